@@ -1,107 +1,1183 @@
-use alloc::{borrow::Cow, boxed::Box, sync::Arc, vec::Vec};
-use core::{fmt::Debug, hash::Hash, mem::ManuallyDrop, ptr::NonNull};
+use alloc::{alloc::Layout, borrow::Cow, boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    convert::Infallible,
+    fmt::Debug,
+    hash::Hash,
+    marker::PhantomData,
+    mem::{align_of, size_of, ManuallyDrop, MaybeUninit},
+    ptr::NonNull,
+};
 
-use crate::length::{InvalidLength, SmallLen, ValidLength};
+use crate::{
+    error::{InvalidLength, LengthError},
+    last_byte::LastByte,
+    length::{const_from_usize, SmallLen, ValidLength},
+    repr::{ReprBody, StorageKind},
+    truncating_into::{report_const_truncation, report_truncation},
+    U24,
+};
 
 #[cold]
-fn truncate_vec<T>(err: InvalidLength<T>, max_len: usize) -> Vec<T> {
+fn truncate_vec<T, LenT: ValidLength>(err: InvalidLength<T, LenT>, max_len: usize) -> Vec<T> {
     let mut value = Vec::from(err.get_inner());
     value.truncate(max_len);
     value
 }
 
+/// An owned [`FixedArray`], unpacked into a normal enum to be matched on.
+enum UnpackedArray<T> {
+    Heap(Box<[T]>),
+    /// Always derived from a `&'static [T]`, where `T: Copy + Sync`.
+    Static(NonNull<[T]>),
+}
+
+#[derive(Clone, Copy)]
+enum Tag {
+    Inline { len: u8 },
+    Heap { high: u8 },
+    Static { high: u8 },
+    BoxedHeap,
+    BoxedStatic,
+}
+
 /// A fixed size array with length provided at creation denoted in a [`ValidLength`], by default [`u32`].
 ///
 /// See module level documentation for more information.
-#[repr(packed)]
+///
+/// This is the size of a pointer and a `LenT`, laid out as the pointer followed by the low bytes of the length, in
+/// little endian. The last byte is then a tag, holding the high bits of the length.
+/// If the length does not fit into these bits, the slice pointer is moved into a [`Box`].
+///
+/// For `LenT`s of 3 bytes or more, this shares its layout with [`FixedString`], and the high bits of the length are
+/// stored in the low nibble of the tag. With [`U24`] this boxes lengths of at least 2^20, and with [`u32`] lengths of
+/// at least 2^28.
+///
+/// Smaller `LenT`s, such as [`u8`] and [`u16`], have a whole byte of the length in the tag, where up to `0x6F` is
+/// stored without boxing for both heap and static arrays. This boxes lengths of at least 112 with [`u8`], and at least
+/// 28672 with [`u16`]. [`FixedString`] stores its own tag after the [`FixedArray`] for these lengths.
+///
+/// The tag only uses some of the values of the last byte, so the rest are available as a niche for any `LenT`.
+/// This keeps nested options, such as `Option<Option<FixedArray<T>>>`, the same size as [`FixedArray`] itself.
+///
+/// Arrays of types with an alignment of 1, such as [`u8`], are stored "inline" instead of allocating if they fit
+/// into the same bytes as the pointer and length, with the length in the low nibble of the last byte. This caps inline
+/// arrays at 15 elements, even for zero sized types.
+///
+/// Types with a larger alignment, such as [`u16`], are never stored inline. [`FixedArray`] has an alignment of 1 to
+/// keep its size down, so it may be stored at any address, and inline elements could not be borrowed as an aligned
+/// `&[T]`.
+///
+/// [`FixedString`]: crate::FixedString
+#[repr(C)]
 pub struct FixedArray<T, LenT: ValidLength = SmallLen> {
-    ptr: NonNull<T>,
-    len: LenT,
+    body: ReprBody<LenT::ArrayRepr>,
+    last: LastByte,
+    marker: PhantomData<T>,
 }
 
-impl<T, LenT: ValidLength> FixedArray<T, LenT> {
-    /// Alias to [`FixedArray::empty`].
-    #[must_use]
-    pub fn new() -> Self {
-        Self::empty()
+/// Returns the size of a [`FixedArray`], which is a pointer and a `LenT`.
+#[must_use]
+pub(crate) const fn get_array_size<LenT>() -> usize {
+    size_of::<usize>() + size_of::<LenT>()
+}
+
+impl<T, LenT: ValidLength> FixedArray<T, LenT> {
+    /// The amount of bytes after the pointer, used to store the low bytes of the length.
+    const LEN_BYTES: usize = size_of::<LenT::ArrayRepr>() - size_of::<NonNull<u8>>();
+
+    /// The amount of bits of the length stored in the body.
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    const LEN_BITS: u32 = (Self::LEN_BYTES * 8) as u32;
+
+    /// If the array is smaller than a [`FixedString`], so the tag does not need to be told apart from inline strings.
+    ///
+    /// [`FixedString`]: crate::FixedString
+    const SMALL_LEN: bool = size_of::<LenT::ArrayRepr>() < size_of::<LenT::InlineStrRepr>();
+
+    /// The tag of inline arrays, with the length in the low nibble.
+    const INLINE_TAG: u8 = if Self::SMALL_LEN {
+        0xE0
+    } else {
+        LastByte::INLINE_SHORT.get()
+    };
+
+    /// The tag of heap arrays, which the high bits of the length are added to.
+    const HEAP_TAG: u8 = if Self::SMALL_LEN {
+        0x00
+    } else {
+        LastByte::HEAP.get()
+    };
+
+    /// The tag of static arrays, which the high bits of the length are added to.
+    const STATIC_TAG: u8 = if Self::SMALL_LEN {
+        0x70
+    } else {
+        LastByte::STATIC.get()
+    };
+
+    /// The number of values of the high bits of the length which can be added to a tag.
+    const HIGH_LIMIT: usize = if Self::SMALL_LEN { 0x70 } else { 0x10 };
+
+    /// Alias to [`FixedArray::empty`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::empty()
+    }
+
+    /// Creates a new, empty [`FixedArray`] that cannot be pushed to.
+    #[must_use]
+    pub const fn empty() -> Self {
+        let empty =
+            core::ptr::slice_from_raw_parts(NonNull::<T>::dangling().as_ptr(), 0).cast_mut();
+
+        // SAFETY: The slice is empty, so is valid for `'static` and never copies a `T`.
+        unsafe { Self::from_static_tagged(NonNull::new_unchecked(empty)) }
+    }
+
+    /// Returns the high bits of `len` if it can be stored without boxing.
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    const fn high_len_bits(len: usize) -> Option<u8> {
+        let high = if Self::LEN_BITS >= usize::BITS {
+            0
+        } else {
+            len >> Self::LEN_BITS
+        };
+
+        if high < Self::HIGH_LIMIT {
+            Some(high as u8)
+        } else {
+            None
+        }
+    }
+
+    const fn from_ptr(ptr: NonNull<u8>, low_len: usize, last: LastByte) -> Self {
+        let low_len = low_len.to_le_bytes();
+
+        let mut rest = [0; 16];
+        let mut i = 0;
+        while i < Self::LEN_BYTES {
+            rest[i] = low_len[i];
+            i += 1;
+        }
+
+        rest[Self::LEN_BYTES] = last.get();
+
+        // SAFETY: `RawArray` holds the pointer, low bytes of the length and last byte in the same layout as `Self`.
+        ManuallyDrop::into_inner(unsafe {
+            RawOrArray {
+                raw: RawArray { ptr, rest },
+            }
+            .array
+        })
+    }
+
+    /// Packs the slice pointer with `tag`, or moves it into a [`Box`] tagged with `boxed_tag` if the length is too long.
+    fn from_slice_ptr(slice: NonNull<[T]>, tag: u8, boxed_tag: LastByte) -> Self {
+        let len = slice.len();
+        if let Some(high) = Self::high_len_bits(len) {
+            let last = LastByte::new(tag + high).expect("high bits should be below the limit");
+            Self::from_ptr(slice.cast(), len, last)
+        } else {
+            Self::from_boxed_slice_ptr(slice, boxed_tag)
+        }
+    }
+
+    /// Returns the last byte for an inline array of `len` elements, if they fit.
+    ///
+    /// The length must fit into the low nibble of the tag, so is at most `0xF`, and `T` must have an alignment of 1,
+    /// as the body itself is not aligned.
+    fn inline_last_byte(len: usize) -> Option<LastByte> {
+        let fits = align_of::<T>() == 1
+            && size_of::<T>()
+                .checked_mul(len)
+                .is_some_and(|size| size <= size_of::<LenT::ArrayRepr>());
+
+        let len = u8::try_from(len).ok().filter(|len| fits && *len <= 0xF)?;
+        LastByte::new(Self::INLINE_TAG | len)
+    }
+
+    /// Moves the elements of `vec` into the body, leaving the allocation to be freed.
+    ///
+    /// `last` must be the result of [`Self::inline_last_byte`] for the length of `vec`.
+    fn from_vec_inline(mut vec: Vec<T>, last: LastByte) -> Self {
+        let mut this = Self {
+            body: ReprBody {
+                inline: LenT::ArrayRepr::default(),
+            },
+            last,
+            marker: PhantomData,
+        };
+
+        // SAFETY: `last` was checked to fit the elements into the body, and `vec` forgets the moved elements.
+        unsafe {
+            let body = core::ptr::addr_of_mut!(this.body).cast::<T>();
+            core::ptr::copy_nonoverlapping(vec.as_ptr(), body, vec.len());
+            vec.set_len(0);
+        }
+
+        this
+    }
+
+    /// Fills an inline array of `len` elements from `f`, so a panic in `f` only drops the elements written so far.
+    ///
+    /// `len` must fit inline, as checked by [`Self::inline_last_byte`].
+    fn from_fn_inline(len: usize, mut f: impl FnMut(usize) -> T) -> Self {
+        match Self::try_from_fn_inline(len, |i| Ok::<_, Infallible>(f(i))) {
+            Ok(new) => new,
+            Err(never) => match never {},
+        }
+    }
+
+    /// As [`Self::from_fn_inline`], but stops and drops the elements written so far if `f` returns an error.
+    fn try_from_fn_inline<E>(
+        len: usize,
+        mut f: impl FnMut(usize) -> Result<T, E>,
+    ) -> Result<Self, E> {
+        let mut new = Self::empty_inline();
+        for i in 0..len {
+            new.push_inline(f(i)?);
+        }
+
+        Ok(new)
+    }
+
+    /// Creates an empty inline array, which `T` must have an alignment of 1 for.
+    fn empty_inline() -> Self {
+        let last = Self::inline_last_byte(0).expect("T should have an alignment of 1");
+        Self::from_vec_inline(Vec::new(), last)
+    }
+
+    /// Pushes `item` onto an inline array, which must have space for it as checked by [`Self::inline_last_byte`].
+    fn push_inline(&mut self, item: T) {
+        let Tag::Inline { len } = self.tag() else {
+            unreachable!("only inline arrays can be pushed to")
+        };
+
+        let len = usize::from(len);
+        let last = Self::inline_last_byte(len + 1).expect("the new length should fit inline");
+
+        // SAFETY: The new length has been checked to fit inline, and `len` is the index past the last element.
+        unsafe {
+            let body = core::ptr::addr_of_mut!(self.body).cast::<T>();
+            body.add(len).write(item);
+        }
+
+        self.last = last;
+    }
+
+    /// Creates a [`FixedArray`] of clones of the parts passed to `push` by `for_each_part`, which should total
+    /// `len` elements. Any elements past `len` are ignored.
+    ///
+    /// This allocates once at the final size, or not at all if the array fits inline.
+    pub(crate) fn from_parts(len: LenT, for_each_part: impl FnOnce(&mut dyn FnMut(&[T]))) -> Self
+    where
+        T: Clone,
+    {
+        let len = len.to_usize();
+        if Self::inline_last_byte(len).is_some() {
+            let mut new = Self::empty_inline();
+            let mut remaining = len;
+            for_each_part(&mut |part| {
+                let part = &part[..part.len().min(remaining)];
+                remaining -= part.len();
+
+                for item in part {
+                    new.push_inline(item.clone());
+                }
+            });
+
+            return new;
+        }
+
+        let mut vec = Vec::with_capacity(len);
+        for_each_part(&mut |part| {
+            let part = &part[..part.len().min(len - vec.len())];
+            vec.extend_from_slice(part);
+        });
+
+        let len = LenT::from_usize(vec.len())
+            .unwrap_or_else(|| unreachable!("at most len elements are pushed"));
+
+        // SAFETY: `len` was taken from the vec.
+        unsafe { Self::from_box(vec.into_boxed_slice(), len) }
+    }
+
+    /// Creates an inline array of `len` elements from the zeroed body.
+    ///
+    /// # Safety
+    /// - `len` must fit inline, as checked by [`Self::inline_last_byte`].
+    /// - Zeroed bytes must be a valid `T`, or `T` must be a [`MaybeUninit`].
+    unsafe fn zeroed_inline(last: LastByte) -> Self {
+        let empty = Self::inline_last_byte(0).expect("len fits inline, so T has an alignment of 1");
+        let mut new = Self::from_vec_inline(Vec::new(), empty);
+        new.last = last;
+        new
+    }
+
+    /// Clones an inline array one element at a time, so a panicking clone only drops the elements written so far.
+    fn clone_inline(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn_inline(self.len().to_usize(), |i| self.as_slice()[i].clone())
+    }
+
+    fn from_boxed_slice_ptr(slice: NonNull<[T]>, boxed_tag: LastByte) -> Self {
+        let ptr = NonNull::from(Box::leak(Box::new(slice))).cast();
+        Self::from_ptr(ptr, 0, boxed_tag)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn from_boxed_heap(boxed: Box<[T]>) -> Self {
+        Self::from_boxed_slice_ptr(NonNull::from(Box::leak(boxed)), LastByte::BOXED_HEAP)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn from_boxed_static(val: &'static [T]) -> Self
+    where
+        T: Copy + Sync,
+    {
+        Self::from_boxed_slice_ptr(NonNull::from(val), LastByte::BOXED_STATIC)
+    }
+
+    /// # Safety
+    /// - `len` must be equal to `ptr.len()`
+    unsafe fn from_box(ptr: Box<[T]>, len: LenT) -> Self {
+        debug_assert_eq!(len.to_usize(), ptr.len());
+
+        if let Some(last) = Self::inline_last_byte(ptr.len()) {
+            return Self::from_vec_inline(ptr.into_vec(), last);
+        }
+
+        let slice = NonNull::from(Box::leak(ptr));
+        Self::from_slice_ptr(slice, Self::HEAP_TAG, LastByte::BOXED_HEAP)
+    }
+
+    /// # Safety
+    /// - `slice` must be valid for `'static`, and `T` must be [`Copy`] and [`Sync`].
+    pub(crate) unsafe fn from_static(slice: NonNull<[T]>) -> Self {
+        Self::from_slice_ptr(slice, Self::STATIC_TAG, LastByte::BOXED_STATIC)
+    }
+
+    /// As [`Self::from_static`], but panics instead of boxing the slice pointer if the length is too long.
+    ///
+    /// # Safety
+    /// - `slice` must be valid for `'static`, and `T` must be [`Copy`] and [`Sync`] unless `slice` is empty.
+    const unsafe fn from_static_tagged(slice: NonNull<[T]>) -> Self {
+        let len = slice.len();
+        let Some(high) = Self::high_len_bits(len) else {
+            panic!("static array is too long to create without allocating");
+        };
+
+        let Some(last) = LastByte::new(Self::STATIC_TAG + high) else {
+            unreachable!()
+        };
+
+        Self::from_ptr(slice.cast(), len, last)
+    }
+
+    /// Converts a `&'static [T]` into a [`FixedArray`].
+    ///
+    /// This method will not copy the array data until the [`FixedArray`] is mutated. It only allocates if the length
+    /// is too long to store in the tag, in which case the slice pointer is boxed, see [`Self::const_from_static_trunc`].
+    ///
+    /// See [`Self::from_vec_trunc`] for truncation behaviour.
+    #[must_use]
+    #[track_caller]
+    pub fn from_static_trunc(val: &'static [T]) -> Self
+    where
+        T: Copy + Sync,
+    {
+        let (array, dropped) = Self::from_static_trunc_report(val);
+        if dropped != 0 {
+            report_truncation::<Self>(val.len(), val.len() - dropped);
+        }
+
+        array
+    }
+
+    /// Converts a `&'static [T]` into a [`FixedArray`], as in [`Self::from_static_trunc`], also returning the number
+    /// of elements that were **truncated**.
+    ///
+    /// Unlike [`Self::from_static_trunc`], this does not call the truncation hook or panic with the
+    /// `strict-truncation` feature, as the caller is told about the truncation.
+    #[must_use]
+    pub fn from_static_trunc_report(val: &'static [T]) -> (Self, usize)
+    where
+        T: Copy + Sync,
+    {
+        let prefix = &val[..val.len().min(LenT::MAX_USIZE)];
+
+        // SAFETY: `prefix` is a `&'static [T]`.
+        let array = unsafe { Self::from_static(NonNull::from(prefix)) };
+        (array, val.len() - prefix.len())
+    }
+
+    /// Converts a `&'static [T]` into a [`FixedArray`] in a `const` context, such as a `const` or `static` item.
+    ///
+    /// This does not call the truncation hook, as `const fn`s cannot, but still panics in debug builds with the
+    /// `strict-truncation` feature.
+    ///
+    /// # Panics
+    /// Panics if the length does not fit into the tag, as the slice pointer cannot be boxed without allocating.
+    /// This happens for lengths of at least 112 with [`u8`], 28672 with [`u16`], 2^20 with [`U24`], 2^28 with [`u32`],
+    /// or 2^60 with [`u64`] and [`usize`].
+    /// Use [`Self::from_static_trunc`] to box the slice pointer instead.
+    #[must_use]
+    pub const fn const_from_static_trunc(val: &'static [T]) -> Self
+    where
+        T: Copy + Sync,
+    {
+        if val.len() > LenT::MAX_USIZE {
+            report_const_truncation();
+        }
+
+        Self::from_static_prefix(val)
+    }
+
+    /// Converts a `&'static [T]` into a [`FixedArray`], as in [`Self::const_from_static_trunc`], also returning the
+    /// number of elements that were **truncated**.
+    ///
+    /// Unlike [`Self::const_from_static_trunc`], this does not panic with the `strict-truncation` feature.
+    ///
+    /// # Panics
+    /// See [`Self::const_from_static_trunc`].
+    #[must_use]
+    pub const fn const_from_static_trunc_report(val: &'static [T]) -> (Self, usize)
+    where
+        T: Copy + Sync,
+    {
+        let dropped = val.len().saturating_sub(LenT::MAX_USIZE);
+        (Self::from_static_prefix(val), dropped)
+    }
+
+    /// Converts the first `LenT::MAX` elements of a `&'static [T]` into a [`FixedArray`], without boxing.
+    const fn from_static_prefix(val: &'static [T]) -> Self
+    where
+        T: Copy + Sync,
+    {
+        let len = if val.len() > LenT::MAX_USIZE {
+            LenT::MAX_USIZE
+        } else {
+            val.len()
+        };
+
+        let slice = core::ptr::slice_from_raw_parts(val.as_ptr(), len).cast_mut();
+
+        // SAFETY: `slice` is a prefix of a `&'static [T]`, so is not null.
+        unsafe { Self::from_static_tagged(NonNull::new_unchecked(slice)) }
+    }
+
+    /// Returns if a static array of `len` elements can be created without boxing its slice pointer.
+    pub(crate) const fn fits_static(len: usize) -> bool {
+        Self::high_len_bits(len).is_some()
+    }
+
+    /// Converts [`Vec<T>`] into [`FixedArray<T>`] while truncating the vector if above the maximum size of `LenT`.
+    ///
+    /// Truncation calls the hook set with [`set_truncation_hook`], and panics in debug builds with the
+    /// `strict-truncation` feature.
+    ///
+    /// [`set_truncation_hook`]: crate::set_truncation_hook
+    #[must_use]
+    #[track_caller]
+    pub fn from_vec_trunc(vec: Vec<T>) -> Self {
+        let original_len = vec.len();
+        let (array, dropped) = Self::from_vec_trunc_report(vec);
+        if dropped != 0 {
+            report_truncation::<Self>(original_len, original_len - dropped);
+        }
+
+        array
+    }
+
+    /// Converts [`Vec<T>`] into [`FixedArray<T>`], as in [`Self::from_vec_trunc`], also returning the number of
+    /// elements that were **truncated**.
+    ///
+    /// Unlike [`Self::from_vec_trunc`], this does not call the truncation hook or panic with the `strict-truncation`
+    /// feature, as the caller is told about the truncation.
+    #[must_use]
+    pub fn from_vec_trunc_report(vec: Vec<T>) -> (Self, usize) {
+        match vec.into_boxed_slice().try_into() {
+            Ok(v) => (v, 0),
+            Err(err) => {
+                let original_len = err.actual();
+                let truncated = Self::try_from(truncate_vec(err, LenT::MAX.to_usize()))
+                    .unwrap_or_else(|_| unreachable!("the truncated vec should fit into LenT"));
+
+                let dropped = original_len - truncated.len().to_usize();
+                (truncated, dropped)
+            }
+        }
+    }
+
+    /// Collects an iterator into a [`FixedArray`], stopping as soon as more than `LenT::MAX` items have been yielded.
+    ///
+    /// The allocation is sized by the iterator's [`Iterator::size_hint`], so exact size iterators will not reallocate.
+    ///
+    /// # Errors
+    /// If the iterator yields more than `LenT::MAX` items, the items collected so far are returned.
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(
+        iter: I,
+    ) -> Result<Self, InvalidLength<T, LenT>> {
+        let max_len = LenT::MAX.to_usize();
+        let iter = iter.into_iter();
+
+        let mut vec = Vec::with_capacity(iter.size_hint().0.min(max_len));
+        vec.extend(iter.take(max_len.saturating_add(1)));
+        vec.try_into()
+    }
+
+    /// Collects an iterator into a [`FixedArray`], **truncating** it to the first `LenT::MAX` items.
+    ///
+    /// The iterator is polled for one item past `LenT::MAX` to tell if it was truncated, then not polled again.
+    /// See [`Self::from_vec_trunc`] for truncation behaviour, with the original length reported as `LenT::MAX + 1`.
+    #[must_use]
+    #[track_caller]
+    pub fn from_iter_trunc<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let max_len = LenT::MAX.to_usize();
+        let iter = iter.into_iter();
+
+        let mut vec = Vec::with_capacity(iter.size_hint().0.min(max_len));
+        vec.extend(iter.take(max_len.saturating_add(1)));
+        Self::from_vec_trunc(vec)
+    }
+
+    /// Creates a [`FixedArray`] of `len` elements, where each element is returned from calling `f` with its index.
+    ///
+    /// This allocates once at the final size, or not at all if the array fits inline.
+    #[must_use]
+    pub fn from_fn(len: LenT, mut f: impl FnMut(usize) -> T) -> Self {
+        let len_usize = len.to_usize();
+        if Self::inline_last_byte(len_usize).is_some() {
+            return Self::from_fn_inline(len_usize, f);
+        }
+
+        let mut vec = Vec::with_capacity(len_usize);
+        vec.extend((0..len_usize).map(&mut f));
+
+        // SAFETY: `vec` has been filled with `len` elements.
+        unsafe { Self::from_box(vec.into_boxed_slice(), len) }
+    }
+
+    /// Creates a [`FixedArray`] of `len` clones of `value`.
+    ///
+    /// This allocates once at the final size, or not at all if the array fits inline.
+    #[must_use]
+    pub fn repeat(value: T, len: LenT) -> Self
+    where
+        T: Clone,
+    {
+        let len_usize = len.to_usize();
+        if Self::inline_last_byte(len_usize).is_some() {
+            return Self::from_fn_inline(len_usize, |_| value.clone());
+        }
+
+        // SAFETY: `vec!` creates exactly `len` elements.
+        unsafe { Self::from_box(alloc::vec![value; len_usize].into_boxed_slice(), len) }
+    }
+
+    /// Concatenates clones of the elements of `parts` into a [`FixedArray`].
+    ///
+    /// The length is calculated up front, so this allocates once at the final size, or not at all if the array fits
+    /// inline.
+    ///
+    /// # Errors
+    /// Returns [`LengthError::TooLong`] if the result is longer than `LenT::MAX`.
+    pub fn concat<S: AsRef<[T]>>(parts: &[S]) -> Result<Self, LengthError<LenT>>
+    where
+        T: Clone,
+    {
+        Self::join(parts, &[])
+    }
+
+    /// Concatenates clones of the elements of `parts` into a [`FixedArray`], with `sep` between each part.
+    ///
+    /// The length is calculated up front, so this allocates once at the final size, or not at all if the array fits
+    /// inline.
+    ///
+    /// # Errors
+    /// Returns [`LengthError::TooLong`] if the result is longer than `LenT::MAX`.
+    pub fn join<I>(parts: I, sep: &[T]) -> Result<Self, LengthError<LenT>>
+    where
+        T: Clone,
+        I: IntoIterator,
+        I::IntoIter: Clone,
+        I::Item: AsRef<[T]>,
+    {
+        let parts = parts.into_iter();
+        let len = joined_len::<LenT>(parts.clone().map(|part| part.as_ref().len()), sep.len())?;
+
+        Ok(Self::from_parts(len, |push| {
+            for (i, part) in parts.enumerate() {
+                if i != 0 {
+                    push(sep);
+                }
+
+                push(part.as_ref());
+            }
+        }))
+    }
+
+    /// Creates a [`FixedArray`] of the elements of `self` repeated `n` times.
+    ///
+    /// Unlike [`Self::repeat`], which repeats a single value, this is the equivalent of [`slice::repeat`]. The length
+    /// is calculated up front, so this allocates once at the final size, or not at all if the array fits inline.
+    ///
+    /// # Errors
+    /// Returns [`LengthError::TooLong`] if the result is longer than `LenT::MAX`.
+    pub fn repeated(&self, n: usize) -> Result<Self, LengthError<LenT>>
+    where
+        T: Clone,
+    {
+        if self.is_empty() {
+            return Ok(Self::new());
+        }
+
+        let len = checked_len::<LenT>(self.len().to_usize().saturating_mul(n))?;
+        Ok(Self::from_parts(len, |push| {
+            for _ in 0..n {
+                push(self);
+            }
+        }))
+    }
+
+    /// Creates a [`FixedArray`] of `len` zeroed elements, using a zeroed allocation.
+    ///
+    /// # Panics
+    /// Panics if the size of the allocation overflows [`isize::MAX`].
+    #[must_use]
+    pub fn zeroed(len: LenT) -> Self
+    where
+        T: Zeroable,
+    {
+        let len_usize = len.to_usize();
+        if let Some(last) = Self::inline_last_byte(len_usize) {
+            // SAFETY: `last` was checked to fit inline, and `T` is `Zeroable`.
+            return unsafe { Self::zeroed_inline(last) };
+        }
+
+        let layout = Layout::array::<T>(len_usize).expect("capacity overflow");
+        let ptr = if layout.size() == 0 {
+            NonNull::<T>::dangling()
+        } else {
+            // SAFETY: The layout has a non-zero size.
+            let ptr = unsafe { alloc::alloc::alloc_zeroed(layout) };
+            NonNull::new(ptr.cast()).unwrap_or_else(|| alloc::alloc::handle_alloc_error(layout))
+        };
+
+        // SAFETY: The allocation was made with the layout of `[T; len]`, and zeroed bytes are a valid `T`.
+        let boxed =
+            unsafe { Box::from_raw(core::ptr::slice_from_raw_parts_mut(ptr.as_ptr(), len_usize)) };
+
+        // SAFETY: `boxed` has `len` elements.
+        unsafe { Self::from_box(boxed, len) }
+    }
+
+    /// Creates a [`FixedArray`] of `len` uninitialized elements, to be initialized then converted with
+    /// [`FixedArray::assume_init`].
+    ///
+    /// This allocates once at the final size, or not at all if the array fits inline.
+    #[must_use]
+    pub fn new_uninit(len: LenT) -> FixedArray<MaybeUninit<T>, LenT> {
+        let len_usize = len.to_usize();
+        if let Some(last) = FixedArray::<MaybeUninit<T>, LenT>::inline_last_byte(len_usize) {
+            // SAFETY: `last` was checked to fit inline, and the elements are `MaybeUninit`.
+            return unsafe { FixedArray::zeroed_inline(last) };
+        }
+
+        let mut vec = Vec::with_capacity(len_usize);
+
+        // SAFETY: The elements are `MaybeUninit`, so do not need to be initialized.
+        unsafe {
+            vec.set_len(len_usize);
+            FixedArray::from_box(vec.into_boxed_slice(), len)
+        }
+    }
+
+    /// Returns the length of the [`FixedArray`].
+    #[must_use]
+    pub const fn len(&self) -> LenT {
+        match const_from_usize(self.slice_ptr().len()) {
+            Some(len) => len,
+            // The length is always derived from a `LenT`.
+            None => unreachable!(),
+        }
+    }
+
+    /// Returns if the length is equal to 0.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.slice_ptr().len() == 0
+    }
+
+    /// Returns where the elements of the [`FixedArray`] are stored.
+    #[must_use]
+    pub const fn storage_kind(&self) -> StorageKind {
+        match self.tag() {
+            Tag::Inline { .. } => StorageKind::Inline,
+            Tag::Heap { .. } | Tag::BoxedHeap => StorageKind::Heap,
+            Tag::Static { .. } | Tag::BoxedStatic => StorageKind::Static,
+        }
+    }
+
+    /// Returns the number of bytes the [`FixedArray`] has allocated on the heap.
+    ///
+    /// This includes the [`Box`] holding the pointer of arrays too long for the tag, but not any heap memory owned by
+    /// the elements themselves.
+    #[must_use]
+    pub const fn heap_bytes(&self) -> usize {
+        let boxed_size = size_of::<NonNull<[T]>>();
+        match self.tag() {
+            Tag::Inline { .. } | Tag::Static { .. } => 0,
+            Tag::Heap { .. } => self.slice_ptr().len() * size_of::<T>(),
+            Tag::BoxedHeap => boxed_size + self.slice_ptr().len() * size_of::<T>(),
+            Tag::BoxedStatic => boxed_size,
+        }
+    }
+
+    /// Converts [`FixedArray<T>`] to [`Vec<T>`], this operation should be cheap.
+    #[must_use]
+    pub fn into_vec(self) -> Vec<T> {
+        self.into()
+    }
+
+    /// Converts [`FixedArray<T>`] to `Box<[T]>`, this operation should be cheap.
+    #[must_use]
+    pub fn into_boxed_slice(self) -> Box<[T]> {
+        self.into()
+    }
+
+    /// Consumes and leaks the [`FixedArray`], returning a mutable reference to its elements for the rest of the
+    /// program.
+    ///
+    /// This is intended for data which is created once and never freed, such as data loaded at startup.
+    /// Heap arrays are leaked without copying, but inline and static arrays are first copied into a new allocation.
+    #[must_use]
+    pub fn leak(self) -> &'static mut [T]
+    where
+        T: 'static,
+    {
+        Box::leak(self.into_boxed_slice())
+    }
+
+    /// Mutates the [`FixedArray`] through a [`Vec`], reusing the existing allocation, then converts it back,
+    /// storing it inline if it now fits.
+    ///
+    /// `self` is cloned first, so it is left unchanged if `f` panics.
+    ///
+    /// # Errors
+    /// If the modified array is longer than `LenT::MAX`, `self` is left unchanged and the modified array is returned.
+    pub fn modify(&mut self, f: impl FnOnce(&mut Vec<T>)) -> Result<(), InvalidLength<T, LenT>>
+    where
+        T: Clone,
+    {
+        let original = self.clone();
+        let mut vec = core::mem::take(self).into_vec();
+        let restore = RestoreOnDrop::new(self, original);
+
+        f(&mut vec);
+        restore.finish(Self::try_from(vec)?);
+        Ok(())
+    }
+
+    /// Mutates the [`FixedArray`] through a [`Vec`], reusing the existing allocation, then converts it back,
+    /// storing it inline if it now fits.
+    ///
+    /// If `f` panics, `self` is left empty.
+    ///
+    /// See [`Self::from_vec_trunc`] for truncation behaviour.
+    #[track_caller]
+    pub fn modify_trunc(&mut self, f: impl FnOnce(&mut Vec<T>)) {
+        let mut vec = core::mem::take(self).into_vec();
+        f(&mut vec);
+
+        *self = Self::from_vec_trunc(vec);
+    }
+
+    /// Converts `&`[`FixedArray<T>`] to `&[T]`, this conversion can be performed by [`core::ops::Deref`].
+    #[must_use]
+    pub const fn as_slice(&self) -> &[T] {
+        // SAFETY: The slice pointer is derived from either the body, `Box<[T]>` or `&'static [T]`.
+        unsafe { &*self.slice_ptr().as_ptr().cast_const() }
+    }
+
+    /// Returns the `&'static [T]` this [`FixedArray`] was created from, if it has not been copied.
+    pub(crate) fn as_static(&self) -> Option<&'static [T]> {
+        // SAFETY: Static arrays are always derived from a `&'static [T]`.
+        (self.storage_kind() == StorageKind::Static)
+            .then(|| unsafe { &*self.slice_ptr().as_ptr().cast_const() })
+    }
+
+    /// Converts `&mut `[`FixedArray<T>`] to `&mut [T]`, this conversion can be performed by [`core::ops::DerefMut`].
+    ///
+    /// If the [`FixedArray`] was created from a `&'static [T]`, this will copy it into a new allocation.
+    #[must_use]
+    pub fn as_slice_mut(&mut self) -> &mut [T] {
+        self
+    }
+
+    /// Creates a [`FixedArray`] of the results of calling `f` on each element, keeping the same length.
+    ///
+    /// If `T` and `U` have the same size and alignment, the allocation is reused.
+    #[must_use]
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> FixedArray<U, LenT> {
+        match self.try_map(|item| Ok::<_, Infallible>(f(item))) {
+            Ok(mapped) => mapped,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Creates a [`FixedArray`] of the results of calling `f` on each element, keeping the same length.
+    ///
+    /// If `T` and `U` have the same size and alignment, the allocation is reused.
+    ///
+    /// # Errors
+    /// Returns the first error returned by `f`, dropping the elements mapped so far.
+    pub fn try_map<U, E>(self, f: impl FnMut(T) -> Result<U, E>) -> Result<FixedArray<U, LenT>, E> {
+        let len = self.len();
+        let len_usize = len.to_usize();
+
+        let same_layout = size_of::<T>() == size_of::<U>() && align_of::<T>() == align_of::<U>();
+        if same_layout && self.storage_kind() == StorageKind::Heap {
+            let UnpackedArray::Heap(boxed) = self.into_unpacked() else {
+                unreachable!("kind has been checked to be Heap")
+            };
+
+            let mapped = try_map_in_place(boxed, f)?;
+
+            // SAFETY: Each element has been mapped, so the length is unchanged.
+            return Ok(unsafe { FixedArray::from_box(mapped, len) });
+        }
+
+        let mut iter = self.into_iter().map(f);
+        if FixedArray::<U, LenT>::inline_last_byte(len_usize).is_some() {
+            return FixedArray::try_from_fn_inline(len_usize, |_| {
+                iter.next()
+                    .unwrap_or_else(|| unreachable!("the length is unchanged"))
+            });
+        }
+
+        let mut vec = Vec::with_capacity(len_usize);
+        for item in iter {
+            vec.push(item?);
+        }
+
+        // SAFETY: Each element has been mapped, so the length is unchanged.
+        Ok(unsafe { FixedArray::from_box(vec.into_boxed_slice(), len) })
+    }
+
+    /// Creates a [`FixedArray`] of the results of calling `f` on a reference to each element, keeping the same length.
+    ///
+    /// This allocates once at the final size, or not at all if the array fits inline.
+    #[must_use]
+    pub fn map_ref<U>(&self, mut f: impl FnMut(&T) -> U) -> FixedArray<U, LenT> {
+        FixedArray::from_fn(self.len(), |i| f(&self.as_slice()[i]))
+    }
+
+    #[allow(clippy::as_conversions)]
+    const fn tag(&self) -> Tag {
+        let last = self.last.get();
+        if last == LastByte::BOXED_HEAP.get() {
+            Tag::BoxedHeap
+        } else if last == LastByte::BOXED_STATIC.get() {
+            Tag::BoxedStatic
+        } else if last >= Self::INLINE_TAG && last - Self::INLINE_TAG <= 0xF {
+            Tag::Inline {
+                len: last - Self::INLINE_TAG,
+            }
+        } else if last >= Self::STATIC_TAG
+            && ((last - Self::STATIC_TAG) as usize) < Self::HIGH_LIMIT
+        {
+            Tag::Static {
+                high: last - Self::STATIC_TAG,
+            }
+        } else {
+            debug_assert!(
+                last >= Self::HEAP_TAG && ((last - Self::HEAP_TAG) as usize) < Self::HIGH_LIMIT,
+                "FixedArray is never a full inline string"
+            );
+
+            Tag::Heap {
+                high: last - Self::HEAP_TAG,
+            }
+        }
+    }
+
+    const fn ptr(&self) -> NonNull<u8> {
+        // SAFETY: Only called for non-inline arrays, which always start with a pointer.
+        unsafe { self.body.ptr }
+    }
+
+    #[allow(clippy::as_conversions)]
+    const fn tagged_len(&self, high: u8) -> usize {
+        let len_ptr = core::ptr::addr_of!(self.body).cast::<u8>();
+
+        let mut low_len = [0_u8; size_of::<usize>()];
+        let mut i = 0;
+        while i < Self::LEN_BYTES {
+            // SAFETY: The body is `LEN_BYTES` bigger than the pointer, and is only made of bytes.
+            low_len[i] = unsafe { *len_ptr.add(size_of::<NonNull<u8>>() + i) };
+            i += 1;
+        }
+
+        usize::from_le_bytes(low_len) | ((high as usize) << Self::LEN_BITS)
+    }
+
+    #[allow(clippy::as_conversions)]
+    const fn slice_ptr(&self) -> NonNull<[T]> {
+        let slice = match self.tag() {
+            Tag::Inline { len } => {
+                core::ptr::slice_from_raw_parts(core::ptr::addr_of!(self.body).cast(), len as usize)
+            }
+            Tag::Heap { high } | Tag::Static { high } => core::ptr::slice_from_raw_parts(
+                self.ptr().as_ptr().cast_const().cast(),
+                self.tagged_len(high),
+            ),
+            // SAFETY: The pointer was derived from `Box::leak` on a `Box<NonNull<[T]>>`.
+            // It is read through a `*const` pointer, as `*mut` cannot be dereferenced in const fns before 1.71.
+            Tag::BoxedHeap | Tag::BoxedStatic => {
+                return unsafe { *self.ptr().cast::<NonNull<[T]>>().as_ptr().cast_const() }
+            }
+        };
+
+        // SAFETY: The body and tagged pointers are never null.
+        unsafe { NonNull::new_unchecked(slice.cast_mut()) }
     }
 
-    /// Creates a new, empty [`FixedArray`] that cannot be pushed to.
-    #[must_use]
-    pub fn empty() -> Self {
-        Self {
-            ptr: NonNull::dangling(),
-            len: LenT::ZERO,
+    fn slice_ptr_mut(&mut self) -> NonNull<[T]> {
+        match self.tag() {
+            Tag::Inline { len } => {
+                NonNull::slice_from_raw_parts(NonNull::from(&mut self.body).cast(), len.into())
+            }
+            _ => self.slice_ptr(),
         }
     }
 
-    /// # Safety
-    /// - `len` must be equal to `ptr.len()`
-    unsafe fn from_box(ptr: Box<[T]>, len: LenT) -> Self {
-        debug_assert_eq!(len.into().to_usize(), ptr.len());
+    fn into_unpacked(self) -> UnpackedArray<T> {
+        let this = ManuallyDrop::new(self);
+        let slice = this.slice_ptr();
 
-        let array_ptr = Box::into_raw(ptr).cast::<T>();
-        Self {
-            ptr: NonNull::new(array_ptr).expect("Box ptr != nullptr"),
-            len,
+        if let Tag::BoxedHeap | Tag::BoxedStatic = this.tag() {
+            // SAFETY: The pointer was derived from `Box::leak`, and `this` is never used again.
+            drop(unsafe { Box::from_raw(this.ptr().cast::<NonNull<[T]>>().as_ptr()) });
         }
-    }
 
-    /// Converts [`Vec<T>`] into [`FixedArray<T>`] while truncating the vector if above the maximum size of `LenT`.
-    #[must_use]
-    pub fn from_vec_trunc(vec: Vec<T>) -> Self {
-        match vec.into_boxed_slice().try_into() {
-            Ok(v) => v,
-            Err(err) => Self::from_vec_trunc(truncate_vec(err, LenT::MAX.to_usize())),
+        match this.storage_kind() {
+            // SAFETY: The slice was derived from `Box::leak`, and `this` is never used again.
+            StorageKind::Heap => UnpackedArray::Heap(unsafe { Box::from_raw(slice.as_ptr()) }),
+            StorageKind::Static => UnpackedArray::Static(slice),
+            StorageKind::Inline => {
+                let mut vec = Vec::with_capacity(slice.len());
+
+                // SAFETY: The elements are moved out of `this`, which is never used again.
+                unsafe {
+                    core::ptr::copy_nonoverlapping(
+                        slice.as_ptr().cast(),
+                        vec.as_mut_ptr(),
+                        slice.len(),
+                    );
+                    vec.set_len(slice.len());
+                }
+
+                UnpackedArray::Heap(vec.into_boxed_slice())
+            }
         }
     }
+}
 
-    /// Returns the length of the [`FixedArray`].
+impl<T, LenT: ValidLength> FixedArray<MaybeUninit<T>, LenT> {
+    /// Converts a [`FixedArray`] of [`MaybeUninit<T>`] into a [`FixedArray<T>`], without copying unless the array is
+    /// static.
+    ///
+    /// # Safety
+    /// Every element must have been initialized, as in [`MaybeUninit::assume_init`].
     #[must_use]
-    pub fn len(&self) -> LenT {
-        self.len
+    pub unsafe fn assume_init(self) -> FixedArray<T, LenT> {
+        // A static `T` must be `Copy + Sync`, which `MaybeUninit<T>` does not prove, so it is moved to the heap.
+        let this = if self.storage_kind() == StorageKind::Static {
+            let len = self.len();
+
+            // SAFETY: `into_boxed_slice` copies the static array to a box of the same length.
+            unsafe { Self::from_box(self.into_boxed_slice(), len) }
+        } else {
+            self
+        };
+
+        let this = ManuallyDrop::new(this);
+
+        // SAFETY: `MaybeUninit<T>` has the same size and alignment as `T`, so the representation is the same,
+        // and the caller has guaranteed that every element is initialized.
+        unsafe { core::ptr::read(core::ptr::addr_of!(*this).cast::<FixedArray<T, LenT>>()) }
     }
+}
 
-    /// Returns if the length is equal to 0.
-    #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.len() == LenT::ZERO
+/// Types which are valid when all of their bytes are zero, for use with [`FixedArray::zeroed`].
+///
+/// # Safety
+/// The all zero bit pattern must be a valid value of the type.
+pub unsafe trait Zeroable {}
+
+macro_rules! impl_zeroable {
+    ($($ty:ty),*) => {
+        $(unsafe impl Zeroable for $ty {})*
+    };
+}
+
+impl_zeroable!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char, U24
+);
+
+/// The layout of a non-inline [`FixedArray`], used to create one in a `const` context.
+#[derive(Clone, Copy)]
+#[repr(C, packed)]
+struct RawArray {
+    ptr: NonNull<u8>,
+    /// The low bytes of the length followed by the last byte, with space for any `LenT`.
+    rest: [u8; 16],
+}
+
+union RawOrArray<T, LenT: ValidLength> {
+    raw: RawArray,
+    array: ManuallyDrop<FixedArray<T, LenT>>,
+}
+
+/// Converts `actual` into `LenT`, for builders which calculate their length up front.
+pub(crate) fn checked_len<LenT: ValidLength>(actual: usize) -> Result<LenT, LengthError<LenT>> {
+    LenT::from_usize(actual).ok_or(LengthError::TooLong {
+        max: LenT::MAX,
+        actual,
+    })
+}
+
+/// Returns the length of `lens` joined with separators of `sep_len`, if it fits into `LenT`.
+///
+/// The length saturates at [`usize::MAX`], so the error holds the full length unless it overflows.
+pub(crate) fn joined_len<LenT: ValidLength>(
+    lens: impl Iterator<Item = usize>,
+    sep_len: usize,
+) -> Result<LenT, LengthError<LenT>> {
+    let mut total = 0_usize;
+    for (i, len) in lens.enumerate() {
+        if i != 0 {
+            total = total.saturating_add(sep_len);
+        }
+
+        total = total.saturating_add(len);
     }
 
-    /// Converts [`FixedArray<T>`] to [`Vec<T>`], this operation should be cheap.
-    #[must_use]
-    pub fn into_vec(self) -> Vec<T> {
-        self.into()
+    checked_len(total)
+}
+
+/// Writes the original value back into `slot` if dropped before [`Self::finish`], so `modify` can leave the value
+/// unchanged if it returns early or panics.
+pub(crate) struct RestoreOnDrop<'a, V> {
+    slot: &'a mut V,
+    original: Option<V>,
+}
+
+impl<'a, V> RestoreOnDrop<'a, V> {
+    pub(crate) fn new(slot: &'a mut V, original: V) -> Self {
+        Self {
+            slot,
+            original: Some(original),
+        }
     }
 
-    /// Converts [`FixedArray<T>`] to `Box<[T]>`, this operation should be cheap.
-    #[must_use]
-    pub fn into_boxed_slice(self) -> Box<[T]> {
-        self.into()
+    /// Writes `value` into the slot instead of the original value.
+    pub(crate) fn finish(mut self, value: V) {
+        self.original = None;
+        *self.slot = value;
     }
+}
 
-    /// Converts `&`[`FixedArray<T>`] to `&[T]`, this conversion can be performed by [`core::ops::Deref`].
-    #[must_use]
-    pub fn as_slice(&self) -> &[T] {
-        self
+impl<V> Drop for RestoreOnDrop<'_, V> {
+    fn drop(&mut self) {
+        if let Some(original) = self.original.take() {
+            *self.slot = original;
+        }
     }
+}
 
-    /// Converts `&mut `[`FixedArray<T>`] to `&mut [T]`, this conversion can be performed by [`core::ops::DerefMut`].
-    #[must_use]
-    pub fn as_slice_mut(&mut self) -> &mut [T] {
-        self
+/// Drops the remaining elements and frees the allocation if [`try_map_in_place`] returns early or panics.
+struct MapInPlaceGuard<T, U> {
+    ptr: *mut T,
+    len: usize,
+    /// The index of the element currently being mapped, with all elements before it already mapped.
+    mapped: usize,
+    marker: PhantomData<U>,
+}
+
+impl<T, U> Drop for MapInPlaceGuard<T, U> {
+    fn drop(&mut self) {
+        // SAFETY: The elements before `mapped` are `U`, the element at `mapped` has been moved out,
+        // and the elements after it are still `T`. The allocation was made for `len` elements of `T`.
+        unsafe {
+            let mapped = core::ptr::slice_from_raw_parts_mut(self.ptr.cast::<U>(), self.mapped);
+            core::ptr::drop_in_place(mapped);
+
+            let remaining = core::ptr::slice_from_raw_parts_mut(
+                self.ptr.add(self.mapped + 1),
+                self.len - self.mapped - 1,
+            );
+            core::ptr::drop_in_place(remaining);
+
+            let allocation =
+                core::ptr::slice_from_raw_parts_mut(self.ptr.cast::<MaybeUninit<T>>(), self.len);
+            drop(Box::from_raw(allocation));
+        }
     }
+}
 
-    /// Converts the [`FixedArray`] to it's original [`Box<T>`].
-    ///
-    /// # Safety
-    /// `self` must never be used again, and it is highly recommended to wrap in [`ManuallyDrop`] before calling.
-    pub(crate) unsafe fn as_box(&mut self) -> Box<[T]> {
-        let slice = self.as_slice_mut();
+/// Maps each element of `boxed` in place, reusing the allocation.
+///
+/// `T` and `U` must have the same size and alignment.
+fn try_map_in_place<T, U, E>(
+    boxed: Box<[T]>,
+    mut f: impl FnMut(T) -> Result<U, E>,
+) -> Result<Box<[U]>, E> {
+    debug_assert!(size_of::<T>() == size_of::<U>() && align_of::<T>() == align_of::<U>());
+
+    let len = boxed.len();
+    let ptr = Box::into_raw(boxed).cast::<T>();
+
+    let mut guard = MapInPlaceGuard {
+        ptr,
+        len,
+        mapped: 0,
+        marker: PhantomData::<U>,
+    };
+
+    while guard.mapped < len {
+        // SAFETY: `mapped` is below `len`, and the element at `mapped` is still a `T`.
+        let item = unsafe { ptr.add(guard.mapped).read() };
+        let item = f(item)?;
+
+        // SAFETY: `T` and `U` have the same layout, and the element at `mapped` has been moved out.
+        unsafe { ptr.add(guard.mapped).cast::<U>().write(item) };
+        guard.mapped += 1;
+    }
+
+    core::mem::forget(guard);
+
+    // SAFETY: Every element has been mapped, and the allocation has the same layout for `U` as for `T`.
+    Ok(unsafe { Box::from_raw(core::ptr::slice_from_raw_parts_mut(ptr.cast::<U>(), len)) })
+}
 
-        // SAFETY: `self` has been derived from `Box<[T]>`
-        unsafe { Box::from_raw(slice) }
+/// Copies a static slice into a new allocation.
+///
+/// # Safety
+/// - `slice` must have been passed to [`FixedArray::from_static`], so `T` is [`Copy`].
+unsafe fn copy_static<T>(slice: NonNull<[T]>) -> Box<[T]> {
+    let mut vec = Vec::with_capacity(slice.len());
+
+    // SAFETY: `T` is `Copy`, and the Vec has the capacity for `slice.len()` elements.
+    unsafe {
+        core::ptr::copy_nonoverlapping(slice.as_ptr().cast::<T>(), vec.as_mut_ptr(), slice.len());
+        vec.set_len(slice.len());
     }
+
+    vec.into_boxed_slice()
 }
 
 unsafe impl<T: Send, LenT: ValidLength> Send for FixedArray<T, LenT> {}
@@ -110,22 +1186,35 @@ unsafe impl<T: Sync, LenT: ValidLength> Sync for FixedArray<T, LenT> {}
 impl<T, LenT: ValidLength> core::ops::Deref for FixedArray<T, LenT> {
     type Target = [T];
     fn deref(&self) -> &Self::Target {
-        // SAFETY: `self.ptr` and `self.len` are both valid and derived from `Box<[T]>`.
-        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len().to_usize()) }
+        // SAFETY: The slice pointer is derived from either the body, `Box<[T]>` or `&'static [T]`.
+        unsafe { self.slice_ptr().as_ref() }
     }
 }
 
 impl<T, LenT: ValidLength> core::ops::DerefMut for FixedArray<T, LenT> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        // SAFETY: `self.ptr` and `self.len` are both valid and derived from `Box<[T]>`.
-        unsafe { core::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len().to_usize()) }
+        if self.storage_kind() == StorageKind::Static {
+            // SAFETY: The slice pointer was passed to `Self::from_static`.
+            let boxed = unsafe { copy_static(self.slice_ptr()) };
+
+            // SAFETY: The length is taken from the same array.
+            *self = unsafe { Self::from_box(boxed, self.len()) };
+        }
+
+        // SAFETY: The slice pointer is derived from either the body or `Box<[T]>`, which are uniquely owned.
+        unsafe { self.slice_ptr_mut().as_mut() }
     }
 }
 
 impl<T, LenT: ValidLength> Drop for FixedArray<T, LenT> {
     fn drop(&mut self) {
-        // SAFETY: We never use `self` again, and we are in the drop impl.
-        unsafe { self.as_box() };
+        if self.storage_kind() == StorageKind::Inline {
+            // SAFETY: The elements are never used again, as we are in the drop impl.
+            unsafe { core::ptr::drop_in_place(self.slice_ptr_mut().as_ptr()) };
+        } else {
+            // SAFETY: `self` is never used again, as we are in the drop impl.
+            drop(unsafe { core::ptr::read(self) }.into_unpacked());
+        }
     }
 }
 
@@ -138,15 +1227,27 @@ impl<T, LenT: ValidLength> Default for FixedArray<T, LenT> {
 
 impl<T: Clone, LenT: ValidLength> Clone for FixedArray<T, LenT> {
     fn clone(&self) -> Self {
-        let ptr = self.as_slice().to_vec().into_boxed_slice();
+        match self.tag() {
+            Tag::Inline { .. } => self.clone_inline(),
+            Tag::Static { .. } => Self {
+                body: self.body,
+                last: self.last,
+                marker: PhantomData,
+            },
+            // SAFETY: The slice pointer was passed to `Self::from_static`.
+            Tag::BoxedStatic => unsafe { Self::from_static(self.slice_ptr()) },
+            Tag::Heap { .. } | Tag::BoxedHeap => {
+                let ptr = self.as_slice().to_vec().into_boxed_slice();
 
-        // SAFETY: The Box::from cannot make the length mismatch.
-        unsafe { Self::from_box(ptr, self.len) }
+                // SAFETY: The Box::from cannot make the length mismatch.
+                unsafe { Self::from_box(ptr, self.len()) }
+            }
+        }
     }
 
     #[allow(clippy::assigning_clones)]
     fn clone_from(&mut self, source: &Self) {
-        if self.len() == source.len() {
+        if self.storage_kind() != StorageKind::Static && self.len() == source.len() {
             self.clone_from_slice(source);
         } else {
             *self = source.clone();
@@ -182,8 +1283,17 @@ impl<T: PartialEq, LenT: ValidLength> PartialEq for FixedArray<T, LenT> {
 impl<T: Eq, LenT: ValidLength> Eq for FixedArray<T, LenT> {}
 
 impl<T: Debug, LenT: ValidLength> Debug for FixedArray<T, LenT> {
+    /// Formats the elements as a slice, or with `{:#?}`, alongside the length and [`StorageKind`].
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        <[T] as Debug>::fmt(self, f)
+        if f.alternate() {
+            f.debug_struct("FixedArray")
+                .field("value", &self.as_slice())
+                .field("len", &self.len())
+                .field("storage", &self.storage_kind())
+                .finish()
+        } else {
+            <[T] as Debug>::fmt(self, f)
+        }
     }
 }
 
@@ -216,10 +1326,11 @@ impl<'a, T, LenT: ValidLength> IntoIterator for &'a mut FixedArray<T, LenT> {
 
 impl<T, LenT: ValidLength> From<FixedArray<T, LenT>> for Box<[T]> {
     fn from(value: FixedArray<T, LenT>) -> Self {
-        let mut value = ManuallyDrop::new(value);
-
-        // SAFETY: We don't use value again, and it is ManuallyDrop.
-        unsafe { value.as_box() }
+        match value.into_unpacked() {
+            UnpackedArray::Heap(boxed) => boxed,
+            // SAFETY: The slice pointer was passed to `FixedArray::from_static`.
+            UnpackedArray::Static(slice) => unsafe { copy_static(slice) },
+        }
     }
 }
 
@@ -237,7 +1348,11 @@ impl<'a, T: Clone, LenT: ValidLength> From<&'a FixedArray<T, LenT>> for Cow<'a,
 
 impl<T: Clone, LenT: ValidLength> From<FixedArray<T, LenT>> for Cow<'_, [T]> {
     fn from(value: FixedArray<T, LenT>) -> Self {
-        Cow::Owned(value.into_vec())
+        match value.into_unpacked() {
+            UnpackedArray::Heap(boxed) => Cow::Owned(boxed.into_vec()),
+            // SAFETY: Static arrays are only created from `&'static [T]`.
+            UnpackedArray::Static(slice) => Cow::Borrowed(unsafe { slice.as_ref() }),
+        }
     }
 }
 
@@ -248,13 +1363,10 @@ impl<T, LenT: ValidLength> From<FixedArray<T, LenT>> for Arc<[T]> {
 }
 
 impl<T, LenT: ValidLength> TryFrom<Box<[T]>> for FixedArray<T, LenT> {
-    type Error = InvalidLength<T>;
+    type Error = InvalidLength<T, LenT>;
     fn try_from(boxed_array: Box<[T]>) -> Result<Self, Self::Error> {
         let Some(len) = LenT::from_usize(boxed_array.len()) else {
-            return Err(InvalidLength::new(
-                core::any::type_name::<LenT>(),
-                boxed_array,
-            ));
+            return Err(InvalidLength::new(boxed_array));
         };
 
         // SAFETY: `len` was derived from the box length.
@@ -263,26 +1375,54 @@ impl<T, LenT: ValidLength> TryFrom<Box<[T]>> for FixedArray<T, LenT> {
 }
 
 impl<T, LenT: ValidLength> TryFrom<Vec<T>> for FixedArray<T, LenT> {
-    type Error = InvalidLength<T>;
+    type Error = InvalidLength<T, LenT>;
     fn try_from(boxed_array: Vec<T>) -> Result<Self, Self::Error> {
         boxed_array.into_boxed_slice().try_into()
     }
 }
 
-macro_rules! impl_array_from {
-    ($($N:expr),*) => {
-        $(
-            impl<T, LenT: ValidLength> From<[T; $N]> for FixedArray<T, LenT> {
-                fn from(val: [T; $N]) -> Self {
-                    Self::try_from(Box::from(val))
-                        .unwrap_or_else(|_| unreachable!(concat!($N, " should be less than {}"), LenT::MAX))
-                }
-            }
-        )*
-    };
+/// Fails to compile if `N` is larger than `LenT::MAX`, once [`Self::CHECK`] is used.
+pub(crate) struct AssertFits<LenT, const N: usize>(PhantomData<LenT>);
+
+impl<LenT: ValidLength, const N: usize> AssertFits<LenT, N> {
+    pub(crate) const CHECK: () = assert!(N <= LenT::MAX_USIZE, "length is larger than LenT::MAX");
+}
+
+/// Fails to compile if a static array of `N` bytes would have to box its slice pointer, once [`Self::CHECK`] is used.
+pub(crate) struct AssertStaticFits<LenT, const N: usize>(PhantomData<LenT>);
+
+impl<LenT: ValidLength, const N: usize> AssertStaticFits<LenT, N> {
+    pub(crate) const CHECK: () = assert!(
+        FixedArray::<u8, LenT>::fits_static(N),
+        "length is too long to store in the tag without allocating"
+    );
+}
+
+impl<T, LenT: ValidLength, const N: usize> From<[T; N]> for FixedArray<T, LenT> {
+    fn from(val: [T; N]) -> Self {
+        let () = AssertFits::<LenT, N>::CHECK;
+
+        if Self::inline_last_byte(N).is_some() {
+            let val = ManuallyDrop::new(val);
+
+            // SAFETY: Each element is read once, and `val` is never dropped.
+            return Self::from_fn_inline(N, |i| unsafe { core::ptr::read(&val[i]) });
+        }
+
+        Self::from(Box::new(val))
+    }
 }
 
-impl_array_from!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
+impl<T, LenT: ValidLength, const N: usize> From<Box<[T; N]>> for FixedArray<T, LenT> {
+    fn from(val: Box<[T; N]>) -> Self {
+        let () = AssertFits::<LenT, N>::CHECK;
+
+        let len = LenT::from_usize(N).unwrap_or_else(|| unreachable!("N has been checked to fit"));
+
+        // SAFETY: `len` was derived from `N`, the length of the box.
+        unsafe { Self::from_box(val, len) }
+    }
+}
 
 impl<T, LenT: ValidLength> AsRef<[T]> for FixedArray<T, LenT> {
     fn as_ref(&self) -> &[T] {
@@ -315,6 +1455,485 @@ where
 #[cfg(feature = "typesize")]
 impl<T: typesize::TypeSize, LenT: ValidLength> typesize::TypeSize for FixedArray<T, LenT> {
     fn extra_size(&self) -> usize {
-        self.iter().map(T::get_size).sum()
+        let boxed_size = match self.tag() {
+            Tag::Inline { .. } | Tag::Heap { .. } | Tag::Static { .. } => 0,
+            Tag::BoxedHeap | Tag::BoxedStatic => size_of::<NonNull<[T]>>(),
+        };
+
+        match self.storage_kind() {
+            StorageKind::Inline => self.iter().map(T::extra_size).sum(),
+            StorageKind::Heap => boxed_size + self.iter().map(T::get_size).sum::<usize>(),
+            StorageKind::Static => boxed_size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Bounded;
+
+    static TABLE: [u16; 5] = [1, 2, 3, 4, 5];
+
+    #[test]
+    fn check_static_roundtrip() {
+        let array = FixedArray::<u16, u8>::from_static_trunc(&TABLE);
+        assert!(array.storage_kind() == StorageKind::Static);
+        assert_eq!(array.len(), 5);
+        assert_eq!(array.as_slice(), &TABLE);
+        assert_eq!(array.as_ptr(), TABLE.as_ptr());
+
+        let cloned = array.clone();
+        assert!(cloned.storage_kind() == StorageKind::Static);
+        assert_eq!(cloned.as_ptr(), TABLE.as_ptr());
+
+        assert_eq!(array.into_vec(), TABLE);
+    }
+
+    #[test]
+    fn check_storage_kind() {
+        let inline = FixedArray::<u8, u8>::from([1, 2, 3]);
+        assert_eq!(inline.storage_kind(), StorageKind::Inline);
+        assert_eq!(inline.heap_bytes(), 0);
+
+        let heap = FixedArray::<u16, u8>::from_vec_trunc(alloc::vec![1; 20]);
+        assert_eq!(heap.storage_kind(), StorageKind::Heap);
+        assert_eq!(heap.heap_bytes(), 40);
+
+        let static_array = FixedArray::<u16, u8>::from_static_trunc(&TABLE);
+        assert_eq!(static_array.storage_kind(), StorageKind::Static);
+        assert_eq!(static_array.heap_bytes(), 0);
+
+        // Too long for the tag, so the slice pointer is boxed.
+        let boxed = FixedArray::<(), U24>::try_from(alloc::vec![(); 1 << 20]).unwrap();
+        assert_eq!(boxed.storage_kind(), StorageKind::Heap);
+        assert_eq!(boxed.heap_bytes(), size_of::<NonNull<[()]>>());
+    }
+
+    #[test]
+    fn check_leak() {
+        let heap = FixedArray::<u16, u8>::from_vec_trunc(alloc::vec![1; 20]);
+        let ptr = heap.as_ptr();
+
+        let leaked = heap.leak();
+        assert_eq!(leaked.as_ptr(), ptr);
+        leaked[0] = 2;
+        assert_eq!(leaked[..2], [2, 1]);
+
+        let inline: &'static mut [u8] = FixedArray::<u8, u8>::from([1, 2]).leak();
+        assert_eq!(inline, &[1, 2]);
+    }
+
+    #[test]
+    fn check_alternate_debug() {
+        let array = FixedArray::<u8, u8>::from([1, 2]);
+        assert_eq!(format!("{array:?}"), "[1, 2]");
+        assert_eq!(
+            format!("{array:#?}"),
+            "FixedArray {\n    value: [\n        1,\n        2,\n    ],\n    len: 2,\n    storage: Inline,\n}"
+        );
+    }
+
+    #[test]
+    fn check_static_truncation() {
+        let long: &'static [u8] = Box::leak(alloc::vec![1; 300].into_boxed_slice());
+        let array = FixedArray::<u8, u8>::from_static_trunc(long);
+        assert_eq!(array.len(), u8::MAX);
+        assert_eq!(array.as_ptr(), long.as_ptr());
+
+        let (array, dropped) = FixedArray::<u8, u8>::from_static_trunc_report(long);
+        assert_eq!((array.len(), dropped), (u8::MAX, 45));
+    }
+
+    #[test]
+    fn check_trunc_report() {
+        let (array, dropped) = FixedArray::<u8, u8>::from_vec_trunc_report(alloc::vec![1; 300]);
+        assert_eq!((array.len(), dropped), (u8::MAX, 45));
+
+        let (array, dropped) = FixedArray::<u8, u8>::from_vec_trunc_report(alloc::vec![1; 3]);
+        assert_eq!((array.as_slice(), dropped), (&[1, 1, 1][..], 0));
+    }
+
+    #[test]
+    fn check_static_to_cow() {
+        let array = FixedArray::<u16, u8>::from_static_trunc(&TABLE);
+
+        let Cow::Borrowed(slice) = Cow::from(array) else {
+            panic!("Expected borrowed slice");
+        };
+
+        assert_eq!(slice.as_ptr(), TABLE.as_ptr());
+    }
+
+    #[test]
+    fn check_static_mutation_copies() {
+        let mut array = FixedArray::<u16, u8>::from_static_trunc(&TABLE);
+        array[0] = 10;
+
+        assert!(array.storage_kind() == StorageKind::Heap);
+        assert_eq!(array.as_slice(), &[10, 2, 3, 4, 5]);
+        assert_eq!(TABLE[0], 1);
+    }
+
+    #[test]
+    fn check_inline_repr() {
+        let array = FixedArray::<u8, u32>::from([1, 2, 3, 4, 5]);
+        assert!(array.storage_kind() == StorageKind::Inline);
+        assert_eq!(array.as_slice(), &[1, 2, 3, 4, 5]);
+
+        let mut cloned = array.clone();
+        assert!(cloned.storage_kind() == StorageKind::Inline);
+        cloned[0] = 10;
+        assert_eq!(cloned.as_slice(), &[10, 2, 3, 4, 5]);
+        assert_eq!(array.as_slice(), &[1, 2, 3, 4, 5]);
+
+        assert_eq!(
+            Cow::from(array),
+            Cow::<[u8]>::Owned(alloc::vec![1, 2, 3, 4, 5])
+        );
+
+        let full = FixedArray::<u8, u32>::from_vec_trunc(alloc::vec![0; 11]);
+        assert!(full.storage_kind() == StorageKind::Inline);
+        let full = FixedArray::<u8, u32>::from_vec_trunc(alloc::vec![0; 12]);
+        assert!(full.storage_kind() == StorageKind::Heap);
+
+        let aligned = FixedArray::<u16, u32>::from([1, 2]);
+        assert!(aligned.storage_kind() == StorageKind::Heap);
+    }
+
+    #[test]
+    fn check_inline_drop() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Clone)]
+        struct DropCounter;
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                DROPPED.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let array = FixedArray::<DropCounter, u8>::from([DropCounter, DropCounter, DropCounter]);
+        assert!(array.storage_kind() == StorageKind::Inline);
+        assert_eq!(DROPPED.load(Ordering::Relaxed), 0);
+
+        drop(array.clone());
+        assert_eq!(DROPPED.load(Ordering::Relaxed), 3);
+
+        assert_eq!(array.into_vec().len(), 3);
+        assert_eq!(DROPPED.load(Ordering::Relaxed), 6);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn check_inline_serde() {
+        let array: FixedArray<u8, u8> = serde_json::from_str("[1, 2, 3]").unwrap();
+        assert!(array.storage_kind() == StorageKind::Inline);
+        assert_eq!(serde_json::to_string(&array).unwrap(), "[1,2,3]");
+    }
+
+    #[test]
+    fn check_boxed_repr() {
+        let heap = FixedArray::<u16, u8>::from_boxed_heap(Box::new(TABLE));
+        assert!(heap.storage_kind() == StorageKind::Heap);
+        assert_eq!(heap.len(), 5);
+        assert_eq!(heap.clone(), heap);
+        assert_eq!(Vec::from(heap), TABLE);
+
+        let mut static_array = FixedArray::<u16, u8>::from_boxed_static(&TABLE);
+        assert!(static_array.storage_kind() == StorageKind::Static);
+        assert_eq!(static_array.clone().as_ptr(), TABLE.as_ptr());
+
+        static_array[4] = 50;
+        assert!(static_array.storage_kind() == StorageKind::Heap);
+        assert_eq!(static_array.as_slice(), &[1, 2, 3, 4, 50]);
+    }
+
+    #[test]
+    fn check_from_iter() {
+        let array = FixedArray::<u16, u8>::try_from_iter(TABLE).unwrap();
+        assert_eq!(array.as_slice(), &TABLE);
+
+        // An infinite iterator must stop as soon as LenT::MAX is exceeded.
+        let err = FixedArray::<u8, u8>::try_from_iter(core::iter::repeat(1)).unwrap_err();
+        assert_eq!(err.get_inner().len(), 256);
+
+        let array = FixedArray::<u8, u8>::from_iter_trunc(core::iter::repeat(1));
+        assert_eq!(array.len(), u8::MAX);
+    }
+
+    #[test]
+    fn check_constructors() {
+        let inline = FixedArray::<u8, u32>::from_fn(4, |i| u8::try_from(i).unwrap());
+        assert!(inline.storage_kind() == StorageKind::Inline);
+        assert_eq!(inline.as_slice(), &[0, 1, 2, 3]);
+
+        let heap = FixedArray::<u16, u32>::from_fn(300, |i| u16::try_from(i).unwrap());
+        assert!(heap.storage_kind() == StorageKind::Heap);
+        assert_eq!(heap[299], 299);
+
+        let repeated = FixedArray::<String, u8>::repeat(String::from("a"), 3);
+        assert_eq!(repeated.as_slice(), &["a", "a", "a"]);
+        assert_eq!(FixedArray::<u8, u8>::repeat(7, 2).as_slice(), &[7, 7]);
+
+        assert!(FixedArray::<u64, u16>::zeroed(1000).iter().all(|v| *v == 0));
+        assert_eq!(FixedArray::<u8, u16>::zeroed(3).as_slice(), &[0, 0, 0]);
+        assert!(FixedArray::<u64, u16>::zeroed(0).is_empty());
+    }
+
+    #[test]
+    fn check_uninit() {
+        let mut array = FixedArray::<String, u8>::new_uninit(3);
+        for (i, item) in array.iter_mut().enumerate() {
+            item.write(i.to_string());
+        }
+
+        // SAFETY: Every element has been written above.
+        let array = unsafe { array.assume_init() };
+        assert_eq!(array.as_slice(), &["0", "1", "2"]);
+
+        let mut inline = FixedArray::<u8, u8>::new_uninit(2);
+        assert!(inline.storage_kind() == StorageKind::Inline);
+        inline[0].write(1);
+        inline[1].write(2);
+
+        // SAFETY: Every element has been written above.
+        assert_eq!(unsafe { inline.assume_init() }.as_slice(), &[1, 2]);
+    }
+
+    #[test]
+    fn check_from_const_array() {
+        let array = FixedArray::<u8, u16>::from([1; 300]);
+        assert_eq!(array.len(), 300);
+        assert!(array.iter().all(|v| *v == 1));
+
+        let inline = FixedArray::<u8, u8>::from([1, 2, 3]);
+        assert!(inline.storage_kind() == StorageKind::Inline);
+        assert_eq!(inline.as_slice(), &[1, 2, 3]);
+
+        let strings = FixedArray::<String, u8>::from([String::from("a"), String::from("b")]);
+        assert_eq!(strings.as_slice(), &["a", "b"]);
+
+        let boxed = FixedArray::<u16, u8>::from(Box::new(TABLE));
+        assert_eq!(boxed.as_slice(), &TABLE);
+    }
+
+    #[test]
+    fn check_map() {
+        let array = FixedArray::<u32, u8>::from([1, 2, 3]);
+        let ptr = array.as_ptr();
+
+        let mapped = array.map(|v| -i32::try_from(v).unwrap());
+        assert_eq!(mapped.as_slice(), &[-1, -2, -3]);
+        assert_eq!(mapped.as_ptr().cast::<u32>(), ptr);
+
+        let inline = FixedArray::<u8, u8>::from([1, 2, 3]).map(|v| v == 2);
+        assert!(inline.storage_kind() == StorageKind::Inline);
+        assert_eq!(inline.as_slice(), &[false, true, false]);
+
+        let widened = FixedArray::<u16, u8>::from_static_trunc(&TABLE).map(u64::from);
+        assert_eq!(widened.as_slice(), &[1, 2, 3, 4, 5]);
+
+        let strings = widened.map_ref(u64::to_string);
+        assert_eq!(strings.as_slice(), &["1", "2", "3", "4", "5"]);
+        assert_eq!(widened.len(), strings.len());
+    }
+
+    #[test]
+    fn check_try_map() {
+        let strings = FixedArray::<String, u8>::from(["1", "2", "x", "4"].map(String::from));
+        let err = strings.clone().try_map(|v| v.parse::<u64>()).unwrap_err();
+        assert_eq!(err, "x".parse::<u64>().unwrap_err());
+
+        // `String` and `Vec<u8>` have the same layout, so this fails part way through mapping in place.
+        let err = strings
+            .clone()
+            .try_map(|v| if v == "x" { Err(v) } else { Ok(v.into_bytes()) });
+        assert_eq!(err.unwrap_err(), "x");
+
+        let lens = strings.try_map(|v| Ok::<_, ()>(v.len())).unwrap();
+        assert_eq!(lens.as_slice(), &[1; 4]);
+
+        let inline = FixedArray::<u8, u8>::from([1, 2, 3]);
+        assert_eq!(
+            inline.clone().try_map(|v| v.checked_sub(2).ok_or(v)),
+            Err(1)
+        );
+        assert_eq!(inline.try_map(i8::try_from).unwrap().as_slice(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn check_modify() {
+        let mut array = FixedArray::<u16, Bounded<u8, 6>>::from_static_trunc(&TABLE);
+        array.modify(|v| v.push(6)).unwrap();
+        assert_eq!(array.as_slice(), &[1, 2, 3, 4, 5, 6]);
+
+        let ptr = array.as_ptr();
+        array.modify(|v| v.reverse()).unwrap();
+        assert_eq!(array.as_ptr(), ptr);
+        assert_eq!(array.as_slice(), &[6, 5, 4, 3, 2, 1]);
+        array.modify(|v| v.reverse()).unwrap();
+
+        let mut too_long = array.clone();
+        let err = too_long.modify(|v| v.push(7)).unwrap_err();
+        assert_eq!(err.get_inner().len(), 7);
+        assert_eq!(too_long, array);
+
+        let ptr = array.as_ptr();
+        array.modify_trunc(|v| v.iter_mut().for_each(|v| *v *= 2));
+        assert_eq!(array.as_ptr(), ptr);
+        assert_eq!(array.as_slice(), &[2, 4, 6, 8, 10, 12]);
+
+        array.modify_trunc(|v| v.extend([14, 16]));
+        assert_eq!(array.as_slice(), &[2, 4, 6, 8, 10, 12]);
+
+        let mut bytes = FixedArray::<u8, u8>::from([1; 20]);
+        bytes.modify_trunc(|v| v.truncate(3));
+        assert!(bytes.storage_kind() == StorageKind::Inline);
+        assert_eq!(bytes.as_slice(), &[1; 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn check_modify_panic() {
+        let mut array = FixedArray::<u16, u8>::from_vec_trunc(alloc::vec![1; 20]);
+        let original = array.clone();
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            array.modify(|v| {
+                v.clear();
+                panic!("modify panicked");
+            })
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(array, original);
+    }
+
+    #[test]
+    fn check_concat() {
+        let inline = FixedArray::<u8, u8>::concat(&[&[1, 2][..], &[3]]).unwrap();
+        assert!(inline.storage_kind() == StorageKind::Inline);
+        assert_eq!(inline.as_slice(), &[1, 2, 3]);
+
+        let heap = FixedArray::<u16, u8>::concat(&[TABLE, TABLE]).unwrap();
+        assert!(heap.storage_kind() == StorageKind::Heap);
+        assert_eq!(heap.len(), 10);
+
+        let strings = [String::from("a"), String::from("b")];
+        let joined =
+            FixedArray::<String, u8>::join([&strings[..], &strings[..1]], &strings[1..]).unwrap();
+        assert_eq!(joined.as_slice(), &["a", "b", "b", "a"]);
+
+        let repeated = FixedArray::<u16, u8>::from_static_trunc(&TABLE)
+            .repeated(3)
+            .unwrap();
+        assert_eq!(repeated.len(), 15);
+        assert_eq!(&repeated.as_slice()[10..], &TABLE);
+
+        let err = FixedArray::<u16, u8>::from_static_trunc(&TABLE)
+            .repeated(52)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            LengthError::TooLong {
+                max: u8::MAX,
+                actual: 260
+            }
+        );
+
+        let err = FixedArray::<u16, Bounded<u8, 6>>::concat(&[TABLE, TABLE]).unwrap_err();
+        assert!(matches!(err, LengthError::TooLong { actual: 10, .. }));
+    }
+
+    fn check_sizes_generic<LenT: ValidLength>(expected: usize) {
+        type TripleOpt<T> = Option<Option<Option<T>>>;
+
+        assert_eq!(size_of::<FixedArray<u64, LenT>>(), expected);
+        assert_eq!(size_of::<FixedArray<u8, LenT>>(), expected);
+        assert_eq!(size_of::<TripleOpt<FixedArray<u64, LenT>>>(), expected);
+        assert_eq!(align_of::<Option<FixedArray<u64, LenT>>>(), 1);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn check_sizes_all_lengths() {
+        check_sizes_generic::<u8>(9);
+        check_sizes_generic::<u16>(10);
+        check_sizes_generic::<U24>(11);
+        check_sizes_generic::<u32>(12);
+        check_sizes_generic::<u64>(16);
+        check_sizes_generic::<usize>(16);
+        check_sizes_generic::<Bounded<u8, 100>>(9);
+        check_sizes_generic::<Bounded<u32, 100>>(12);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn check_u24_tag_limit() {
+        let boxed_size = size_of::<NonNull<[u8]>>();
+
+        let fits = FixedArray::<u8, U24>::try_from(alloc::vec![1; (1 << 20) - 1]).unwrap();
+        assert_eq!(fits.heap_bytes(), (1 << 20) - 1);
+
+        let boxed = FixedArray::<u8, U24>::try_from(alloc::vec![1; 1 << 20]).unwrap();
+        assert_eq!(boxed.heap_bytes(), (1 << 20) + boxed_size);
+        assert_eq!(boxed.len().to_usize(), 1 << 20);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn check_small_len_tag_limits() {
+        let boxed_size = size_of::<NonNull<[u16]>>();
+
+        let heap = FixedArray::<u16, u8>::from_vec_trunc(alloc::vec![1; 111]);
+        assert_eq!(heap.heap_bytes(), 222);
+        let heap = FixedArray::<u16, u8>::from_vec_trunc(alloc::vec![1; 112]);
+        assert_eq!(heap.heap_bytes(), 224 + boxed_size);
+        assert_eq!(heap.len(), 112);
+
+        let long: &'static [u16] = Box::leak(alloc::vec![1; 28672].into_boxed_slice());
+        let static_array = FixedArray::<u16, u16>::from_static_trunc(&long[..28671]);
+        assert_eq!(static_array.storage_kind(), StorageKind::Static);
+        assert_eq!(static_array.heap_bytes(), 0);
+
+        let static_array = FixedArray::<u16, u16>::from_static_trunc(long);
+        assert_eq!(static_array.storage_kind(), StorageKind::Static);
+        assert_eq!(static_array.heap_bytes(), boxed_size);
+        assert_eq!(static_array.as_ptr(), long.as_ptr());
+        assert_eq!(static_array.clone().len(), 28672);
+
+        let heap = FixedArray::<u16, u16>::from_vec_trunc(alloc::vec![1; 28671]);
+        assert_eq!((heap.heap_bytes(), heap.len()), (28671 * 2, 28671));
+        let heap = FixedArray::<u16, u16>::from_vec_trunc(alloc::vec![1; u16::MAX.into()]);
+        assert_eq!(heap.len(), u16::MAX);
+        assert_eq!(heap.heap_bytes(), usize::from(u16::MAX) * 2 + boxed_size);
+
+        let inline = FixedArray::<u8, u16>::from([1; 9]);
+        assert_eq!(inline.storage_kind(), StorageKind::Inline);
+        assert_eq!(inline.as_slice(), &[1; 9]);
+        let inline = FixedArray::<u8, u8>::from([1; 8]);
+        assert_eq!(inline.storage_kind(), StorageKind::Inline);
+        assert_eq!(inline.clone().as_slice(), &[1; 8]);
+    }
+
+    #[test]
+    fn check_const() {
+        const EMPTY: FixedArray<String> = FixedArray::new();
+        static STATIC: FixedArray<u16, u8> = FixedArray::const_from_static_trunc(&TABLE);
+        static TRUNCATED: (FixedArray<u16, Bounded<u8, 2>>, usize) =
+            FixedArray::const_from_static_trunc_report(&TABLE);
+
+        assert!(EMPTY.is_empty());
+        assert_eq!(EMPTY.as_slice(), Vec::<String>::new());
+
+        assert!(STATIC.storage_kind() == StorageKind::Static);
+        assert_eq!(STATIC.as_slice(), &TABLE);
+        assert_eq!(STATIC.clone().as_slice(), &TABLE);
+        assert_eq!(TRUNCATED.0.as_slice(), &TABLE[..2]);
+        assert_eq!(TRUNCATED.1, 3);
     }
 }
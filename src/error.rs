@@ -0,0 +1,532 @@
+//! The errors returned when converting a value that does not fit.
+//!
+//! Each error gives back the original value, if it was moved into the conversion, along with the maximum length
+//! and the length that was actually given. [`LengthError`] summarises these, for callers that only need the reason.
+use alloc::{boxed::Box, sync::Arc};
+use core::{marker::PhantomData, str::Utf8Error};
+
+use crate::{length::SmallLen, Bounded, FixedArray, ValidLength};
+
+/// The error returned when a slice is longer than `LenT::MAX`, holding the original value.
+#[derive(Debug)]
+pub struct InvalidLength<T, LenT: ValidLength = SmallLen> {
+    original: Box<[T]>,
+    marker: PhantomData<LenT>,
+}
+
+impl<T, LenT: ValidLength> InvalidLength<T, LenT> {
+    #[cold]
+    #[track_caller]
+    pub(crate) fn new(original: Box<[T]>) -> Self {
+        Self {
+            original,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the original [`Box<[T]>`] that could not be converted from.
+    #[must_use]
+    pub fn get_inner(self) -> Box<[T]> {
+        self.original
+    }
+
+    /// Returns the maximum length that could have been converted, `LenT::MAX`.
+    #[must_use]
+    pub fn max(&self) -> LenT {
+        LenT::MAX
+    }
+
+    /// Returns the length of the original value.
+    #[must_use]
+    pub fn actual(&self) -> usize {
+        self.original.len()
+    }
+
+    /// Returns the name of `LenT`, the length type that was converted into.
+    #[must_use]
+    pub fn type_name(&self) -> &'static str {
+        core::any::type_name::<LenT>()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: core::fmt::Debug, LenT: ValidLength> std::error::Error for InvalidLength<T, LenT> {}
+
+impl<T, LenT: ValidLength> core::fmt::Display for InvalidLength<T, LenT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Cannot fit {} into {}", self.actual(), self.type_name())
+    }
+}
+
+/// The error returned when a string is longer than `LenT::MAX` bytes, holding the original value.
+#[derive(Debug)]
+pub struct InvalidStrLength<LenT: ValidLength = SmallLen> {
+    original: Box<str>,
+    marker: PhantomData<LenT>,
+}
+
+impl<LenT: ValidLength> InvalidStrLength<LenT> {
+    /// Returns the original [`Box<str>`] that could not be converted from.
+    #[must_use]
+    pub fn get_inner(self) -> Box<str> {
+        self.original
+    }
+
+    /// Returns the maximum length in bytes that could have been converted, `LenT::MAX`.
+    #[must_use]
+    pub fn max(&self) -> LenT {
+        LenT::MAX
+    }
+
+    /// Returns the length of the original value in bytes.
+    #[must_use]
+    pub fn actual(&self) -> usize {
+        self.original.len()
+    }
+
+    /// Returns the name of `LenT`, the length type that was converted into.
+    #[must_use]
+    pub fn type_name(&self) -> &'static str {
+        core::any::type_name::<LenT>()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<LenT: ValidLength> std::error::Error for InvalidStrLength<LenT> {}
+
+impl<LenT: ValidLength> core::fmt::Display for InvalidStrLength<LenT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Cannot fit {} into {}", self.actual(), self.type_name())
+    }
+}
+
+impl<LenT: ValidLength> TryFrom<InvalidLength<u8, LenT>> for InvalidStrLength<LenT> {
+    type Error = Utf8Error;
+
+    fn try_from(value: InvalidLength<u8, LenT>) -> Result<Self, Self::Error> {
+        let original = if let Err(err) = core::str::from_utf8(&value.original) {
+            return Err(err);
+        } else {
+            // SAFETY: `value.original` was just checked to be valid UTF-8 by `core::str::from_utf8`.
+            unsafe { alloc::str::from_boxed_utf8_unchecked(value.original) }
+        };
+
+        Ok(Self {
+            original,
+            marker: PhantomData,
+        })
+    }
+}
+
+/// The error returned when a shared slice or string is longer than `LenT::MAX`, holding the original value.
+#[derive(Debug)]
+pub struct InvalidSharedLength<T: ?Sized, LenT: ValidLength = SmallLen> {
+    original: Arc<T>,
+    marker: PhantomData<LenT>,
+}
+
+impl<T: ?Sized, LenT: ValidLength> InvalidSharedLength<T, LenT> {
+    #[cold]
+    #[track_caller]
+    pub(crate) fn new(original: Arc<T>) -> Self {
+        Self {
+            original,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the original [`Arc<T>`] that could not be converted from.
+    #[must_use]
+    pub fn get_inner(self) -> Arc<T> {
+        self.original
+    }
+
+    /// Returns the maximum length that could have been converted, `LenT::MAX`.
+    #[must_use]
+    pub fn max(&self) -> LenT {
+        LenT::MAX
+    }
+
+    /// Returns the name of `LenT`, the length type that was converted into.
+    #[must_use]
+    pub fn type_name(&self) -> &'static str {
+        core::any::type_name::<LenT>()
+    }
+}
+
+impl<T, LenT: ValidLength> InvalidSharedLength<[T], LenT> {
+    /// Returns the length of the original value.
+    #[must_use]
+    pub fn actual(&self) -> usize {
+        self.original.len()
+    }
+}
+
+impl<LenT: ValidLength> InvalidSharedLength<str, LenT> {
+    /// Returns the length of the original value in bytes.
+    #[must_use]
+    pub fn actual(&self) -> usize {
+        self.original.len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: core::fmt::Debug, LenT: ValidLength> std::error::Error for InvalidSharedLength<[T], LenT> {}
+
+#[cfg(feature = "std")]
+impl<LenT: ValidLength> std::error::Error for InvalidSharedLength<str, LenT> {}
+
+impl<T, LenT: ValidLength> core::fmt::Display for InvalidSharedLength<[T], LenT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Cannot fit {} into {}", self.actual(), self.type_name())
+    }
+}
+
+impl<LenT: ValidLength> core::fmt::Display for InvalidSharedLength<str, LenT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Cannot fit {} into {}", self.actual(), self.type_name())
+    }
+}
+
+/// The error returned when a string is longer than the maximum of a [`UnitString`], holding the original value.
+///
+/// The lengths are counted in the [`LengthUnit`] of the [`UnitString`], or in bytes if the string did not fit into
+/// its `LenT`.
+///
+/// [`UnitString`]: crate::UnitString
+/// [`LengthUnit`]: crate::LengthUnit
+#[derive(Debug)]
+pub struct InvalidUnitLength<LenT: ValidLength = SmallLen> {
+    type_name: &'static str,
+    unit: &'static str,
+    max: LenT,
+    actual: usize,
+    original: Box<str>,
+}
+
+impl<LenT: ValidLength> InvalidUnitLength<LenT> {
+    #[cold]
+    pub(crate) fn new(
+        type_name: &'static str,
+        unit: &'static str,
+        max: LenT,
+        actual: usize,
+        original: Box<str>,
+    ) -> Self {
+        Self {
+            type_name,
+            unit,
+            max,
+            actual,
+            original,
+        }
+    }
+
+    /// Returns the original [`Box<str>`] that could not be converted from.
+    #[must_use]
+    pub fn get_inner(self) -> Box<str> {
+        self.original
+    }
+
+    /// Returns the maximum length that could have been converted, counted in [`Self::unit`].
+    ///
+    /// This is at most `LenT::MAX`, as every unit takes at least one byte.
+    #[must_use]
+    pub fn max(&self) -> LenT {
+        self.max
+    }
+
+    /// Returns the length of the original value, counted in [`Self::unit`].
+    #[must_use]
+    pub fn actual(&self) -> usize {
+        self.actual
+    }
+
+    /// Returns the name of the unit the lengths are counted in.
+    #[must_use]
+    pub fn unit(&self) -> &'static str {
+        self.unit
+    }
+
+    /// Returns the name of the type that was converted into.
+    #[must_use]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+#[cfg(feature = "std")]
+impl<LenT: ValidLength> std::error::Error for InvalidUnitLength<LenT> {}
+
+impl<LenT: ValidLength> core::fmt::Display for InvalidUnitLength<LenT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Cannot fit {} {} into {}",
+            self.actual, self.unit, self.type_name,
+        )
+    }
+}
+
+impl<LenT: ValidLength> From<InvalidStrLength<LenT>> for InvalidUnitLength<LenT> {
+    fn from(value: InvalidStrLength<LenT>) -> Self {
+        Self {
+            type_name: value.type_name(),
+            unit: "bytes",
+            max: LenT::MAX,
+            actual: value.actual(),
+            original: value.original,
+        }
+    }
+}
+
+/// The error returned when converting a value above `MAX` into a [`Bounded`].
+///
+/// [`Bounded`]: crate::Bounded
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TryFromBoundedError {
+    pub(crate) max: usize,
+    pub(crate) actual: usize,
+}
+
+impl TryFromBoundedError {
+    /// Returns the `MAX` of the [`Bounded`].
+    ///
+    /// [`Bounded`]: crate::Bounded
+    #[must_use]
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Returns the value that could not be converted.
+    #[must_use]
+    pub fn actual(&self) -> usize {
+        self.actual
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryFromBoundedError {}
+
+impl core::fmt::Display for TryFromBoundedError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} is above the maximum of {}", self.actual, self.max)
+    }
+}
+
+/// The error returned by [`FixedString::from_utf8`], holding the bytes that were not valid UTF-8.
+///
+/// [`FixedString::from_utf8`]: crate::FixedString::from_utf8
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FromUtf8Error<LenT: ValidLength = SmallLen> {
+    pub(crate) bytes: FixedArray<u8, LenT>,
+    pub(crate) error: Utf8Error,
+}
+
+impl<LenT: ValidLength> FromUtf8Error<LenT> {
+    /// Returns the original [`FixedArray<u8>`] that could not be converted from.
+    #[must_use]
+    pub fn get_inner(self) -> FixedArray<u8, LenT> {
+        self.bytes
+    }
+
+    /// Returns the bytes that could not be converted from.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the [`Utf8Error`] describing where the bytes stopped being valid UTF-8.
+    #[must_use]
+    pub fn utf8_error(&self) -> Utf8Error {
+        self.error
+    }
+}
+
+#[cfg(feature = "std")]
+impl<LenT: ValidLength> std::error::Error for FromUtf8Error<LenT> {}
+
+impl<LenT: ValidLength> core::fmt::Display for FromUtf8Error<LenT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.error, f)
+    }
+}
+
+/// The reason a value could not be converted, without the original value.
+///
+/// Each error in this module converts into this, so it can be used with `?` to tell a value that is too long apart
+/// from one that is not valid UTF-8. [`TryFromBoundedError`] only converts into the [`LengthError`] of a [`Bounded`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LengthError<LenT: ValidLength = SmallLen> {
+    /// The value was `actual` elements, bytes or [`LengthUnit`]s long, which is longer than `max`.
+    ///
+    /// [`LengthUnit`]: crate::LengthUnit
+    TooLong { max: LenT, actual: usize },
+    /// The value was not valid UTF-8.
+    InvalidUtf8(Utf8Error),
+}
+
+#[cfg(feature = "std")]
+impl<LenT: ValidLength> std::error::Error for LengthError<LenT> {}
+
+impl<LenT: ValidLength> core::fmt::Display for LengthError<LenT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooLong { actual, .. } => write!(
+                f,
+                "Cannot fit {actual} into {}",
+                core::any::type_name::<LenT>()
+            ),
+            Self::InvalidUtf8(err) => core::fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl<LenT: ValidLength> From<Utf8Error> for LengthError<LenT> {
+    fn from(value: Utf8Error) -> Self {
+        Self::InvalidUtf8(value)
+    }
+}
+
+impl<LenT: ValidLength> From<FromUtf8Error<LenT>> for LengthError<LenT> {
+    fn from(value: FromUtf8Error<LenT>) -> Self {
+        Self::InvalidUtf8(value.error)
+    }
+}
+
+impl<T, LenT: ValidLength> From<InvalidLength<T, LenT>> for LengthError<LenT> {
+    fn from(value: InvalidLength<T, LenT>) -> Self {
+        Self::TooLong {
+            max: value.max(),
+            actual: value.actual(),
+        }
+    }
+}
+
+impl<LenT: ValidLength> From<InvalidStrLength<LenT>> for LengthError<LenT> {
+    fn from(value: InvalidStrLength<LenT>) -> Self {
+        Self::TooLong {
+            max: value.max(),
+            actual: value.actual(),
+        }
+    }
+}
+
+impl<LenT: ValidLength> From<InvalidUnitLength<LenT>> for LengthError<LenT> {
+    fn from(value: InvalidUnitLength<LenT>) -> Self {
+        Self::TooLong {
+            max: value.max(),
+            actual: value.actual(),
+        }
+    }
+}
+
+/// The maximum is taken from the [`Bounded`] being converted into.
+impl<Int: ValidLength, const MAX: usize> From<TryFromBoundedError>
+    for LengthError<Bounded<Int, MAX>>
+where
+    Bounded<Int, MAX>: ValidLength,
+{
+    fn from(value: TryFromBoundedError) -> Self {
+        Self::TooLong {
+            max: Bounded::<Int, MAX>::MAX,
+            actual: value.actual(),
+        }
+    }
+}
+
+impl<T, LenT: ValidLength> From<InvalidSharedLength<[T], LenT>> for LengthError<LenT> {
+    fn from(value: InvalidSharedLength<[T], LenT>) -> Self {
+        Self::TooLong {
+            max: value.max(),
+            actual: value.actual(),
+        }
+    }
+}
+
+impl<LenT: ValidLength> From<InvalidSharedLength<str, LenT>> for LengthError<LenT> {
+    fn from(value: InvalidSharedLength<str, LenT>) -> Self {
+        Self::TooLong {
+            max: value.max(),
+            actual: value.actual(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{boxed::Box, string::String, vec};
+
+    use super::*;
+    use crate::{FixedArray, FixedString, SharedFixedArray, U24};
+
+    #[test]
+    fn check_lengths() {
+        let err = FixedArray::<u8, u8>::try_from(vec![0; 300]).unwrap_err();
+        assert_eq!(err.max(), u8::MAX);
+        assert_eq!(err.actual(), 300);
+        assert_eq!(err.type_name(), "u8");
+        assert_eq!(err.to_string(), "Cannot fit 300 into u8");
+
+        let err = FixedString::<U24>::try_from(String::from("a").repeat(1 << 24)).unwrap_err();
+        assert_eq!(err.max(), U24::MAX);
+        assert_eq!(err.actual(), 1 << 24);
+        assert!(err.type_name().ends_with("U24"));
+
+        let err = SharedFixedArray::<u16, u8>::try_from(Arc::from(vec![0; 256])).unwrap_err();
+        assert_eq!(err.max(), u8::MAX);
+        assert_eq!(err.actual(), 256);
+
+        let err = crate::Bounded::<u8, 10>::try_from(11).unwrap_err();
+        assert_eq!((err.max(), err.actual()), (10, 11));
+    }
+
+    fn parse(bytes: Box<[u8]>) -> Result<FixedString<u8>, LengthError<u8>> {
+        let array = FixedArray::<u8, u8>::try_from(bytes)?;
+        Ok(FixedString::from_utf8(array)?)
+    }
+
+    #[test]
+    fn check_length_error() {
+        assert_eq!(parse(Box::from(*b"hello")).unwrap(), "hello");
+        assert_eq!(
+            parse(vec![b'a'; 256].into_boxed_slice()),
+            Err(LengthError::TooLong {
+                max: u8::MAX,
+                actual: 256
+            })
+        );
+        assert!(matches!(
+            parse(Box::from(*b"\xFF")),
+            Err(LengthError::InvalidUtf8(_))
+        ));
+
+        let err = FixedArray::<u8, u8>::try_from(vec![0xFF; 256]).unwrap_err();
+        let err = InvalidStrLength::try_from(err).map_err(LengthError::<u8>::from);
+        assert!(matches!(err, Err(LengthError::InvalidUtf8(_))));
+    }
+
+    #[test]
+    fn check_length_error_from_unit_and_bounded() {
+        let err = crate::CharString::<3, u8>::try_from(String::from("abcd")).unwrap_err();
+        assert_eq!((err.max(), err.actual()), (3, 4));
+        assert_eq!(
+            LengthError::from(err),
+            LengthError::TooLong { max: 3, actual: 4 }
+        );
+
+        // The unit maximum is capped at `LenT::MAX`, as every unit takes at least one byte.
+        let err = crate::CharString::<300, u8>::try_from("a".repeat(256)).unwrap_err();
+        assert_eq!((err.max(), err.actual()), (u8::MAX, 256));
+
+        let err = Bounded::<u8, 10>::try_from(11_usize).unwrap_err();
+        assert_eq!(
+            LengthError::<Bounded<u8, 10>>::from(err),
+            LengthError::TooLong {
+                max: Bounded::<u8, 10>::MAX,
+                actual: 11
+            }
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use core::mem::size_of;
 
-use crate::ValidLength;
+use crate::last_byte::LastByte;
 
 #[cfg(feature = "typesize")]
 use typesize::TypeSize;
@@ -10,74 +10,59 @@ pub(crate) trait TypeSize {}
 #[cfg(not(feature = "typesize"))]
 impl<T> TypeSize for T {}
 
+/// Returns the maximum length of an inline string, which is also the size of a [`FixedString`].
+///
+/// Lengths of 3 bytes or more give up their top byte to the tag stored in [`LastByte`],
+/// smaller lengths cannot spare it and so need an extra byte.
+///
+/// [`FixedString`]: crate::FixedString
 #[must_use]
 pub(crate) const fn get_heap_threshold<LenT>() -> usize {
-    core::mem::size_of::<usize>() + core::mem::size_of::<LenT>()
-}
-
-#[cfg(not(feature = "nightly"))]
-fn find_term_index(haystack: [u8; 16], term: u8, fallback: u8) -> u8 {
-    let mut term_position = fallback;
-
-    // Avoid enumerate to keep the index as a u8
-    for (pos, byte) in (0..16).zip(haystack) {
-        if byte == term {
-            // Do not break, it reduces performance a ton due to branching.
-            term_position = pos;
-        }
-    }
-
-    term_position
-}
-
-#[cfg(feature = "nightly")]
-fn find_term_index(haystack: [u8; 16], term: u8, fallback: u8) -> u8 {
-    use core::simd::prelude::*;
-
-    // Make simd array of [term; 16]
-    let term_arr = u8x16::splat(term);
-    // Convert haystack into simd array
-    let elements = u8x16::from_array(haystack);
-    // Compare each element of the simd array, converting back to a scalar bitmask.
-    let scalar_mask = term_arr.simd_eq(elements).to_bitmask();
-
-    if scalar_mask == 0 {
-        // If the mask is 0, the terminator was not included, so return fallback.
-        fallback
+    let len_size = size_of::<LenT>();
+    if len_size >= 3 {
+        size_of::<usize>() + len_size
     } else {
-        // The mask has the terminator as the last character with a bit set, so use trailing zeros.
-        u8::try_from(scalar_mask.trailing_zeros()).unwrap()
+        size_of::<usize>() + len_size + 1
     }
 }
 
 #[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
 #[derive(Clone)]
+#[repr(C)]
 pub(crate) struct InlineString<StrRepr: Copy + AsRef<[u8]> + AsMut<[u8]> + Default + TypeSize> {
     arr: StrRepr,
+    last: LastByte,
 }
 
 impl<StrRepr: Copy + AsRef<[u8]> + AsMut<[u8]> + Default + TypeSize> InlineString<StrRepr> {
-    const TERMINATOR: u8 = 0xFF;
-
-    fn max_len() -> usize {
-        StrRepr::default().as_ref().len()
+    const fn max_len() -> usize {
+        size_of::<Self>()
     }
 
     #[inline]
-    fn from_len_and_write(len: usize, write: impl FnOnce(&mut [u8])) -> Option<Self> {
-        let mut arr = StrRepr::default();
-        if len > size_of::<Self>() {
+    pub fn from_len_and_write(len: usize, write: impl FnOnce(&mut [u8])) -> Option<Self> {
+        if len > Self::max_len() {
             return None;
         }
 
-        write(arr.as_mut());
-
-        if len != Self::max_len() {
-            // 0xFF terminate the string, to gain an extra inline character
-            arr.as_mut()[len] = Self::TERMINATOR;
-        }
+        let mut buf = [0_u8; 16];
+        write(&mut buf);
 
-        Some(Self { arr })
+        let mut arr = StrRepr::default();
+        let arr_len = arr.as_ref().len();
+        arr.as_mut().copy_from_slice(&buf[..arr_len]);
+
+        let last = if len == Self::max_len() {
+            // The string fills the last byte too, which is always below the tags.
+            LastByte::new(buf[arr_len]).expect("the final byte of a UTF-8 string is never a tag")
+        } else {
+            // Store the length in the last byte, which cannot be the end of a UTF-8 string.
+            let len = u8::try_from(len).expect("inline length should fit in the low nibble");
+            LastByte::new(LastByte::INLINE_SHORT.get() | len)
+                .expect("inline length should fit in the low nibble")
+        };
+
+        Some(Self { arr, last })
     }
 
     pub fn from_str(val: &str) -> Option<Self> {
@@ -92,18 +77,28 @@ impl<StrRepr: Copy + AsRef<[u8]> + AsMut<[u8]> + Default + TypeSize> InlineStrin
         })
     }
 
-    pub fn len(&self) -> u8 {
-        // Copy to a temporary, 16 byte array to allow for SIMD impl.
-        let mut buf = [0_u8; 16];
-        buf[..Self::max_len()].copy_from_slice(self.arr.as_ref());
+    pub fn into_parts(self) -> (StrRepr, LastByte) {
+        (self.arr, self.last)
+    }
 
-        // This call is different depending on nightly or not.
-        find_term_index(buf, Self::TERMINATOR, Self::max_len().try_into().unwrap())
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    pub const fn len(&self) -> u8 {
+        let last = self.last.get();
+        if last >= LastByte::INLINE_SHORT.get() {
+            last - LastByte::INLINE_SHORT.get()
+        } else {
+            // Inline strings are at most 16 bytes.
+            Self::max_len() as u8
+        }
     }
 
-    pub fn as_str(&self) -> &str {
-        let len: usize = self.len().to_usize();
-        let bytes = &self.arr.as_ref()[..len];
+    #[allow(clippy::as_conversions)]
+    pub const fn as_str(&self) -> &str {
+        let len = self.len() as usize;
+
+        // SAFETY: `Self` is `repr(C)` and only made of bytes, so `arr` is directly followed by `last`.
+        let bytes =
+            unsafe { core::slice::from_raw_parts(core::ptr::addr_of!(*self).cast::<u8>(), len) };
 
         // SAFETY: Accessing only initialised UTF8 bytes based on the length.
         unsafe { core::str::from_utf8_unchecked(bytes) }
@@ -115,6 +110,7 @@ impl<Repr: Copy + AsRef<[u8]> + AsMut<[u8]> + Default + TypeSize> Copy for Inlin
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ValidLength;
 
     fn check_roundtrip<Repr>(original: &str)
     where
@@ -125,7 +121,7 @@ mod tests {
     }
 
     fn check_roundtrip_repr<Repr: Copy + AsRef<[u8]> + AsMut<[u8]> + Default + TypeSize>() {
-        for i in 0..=core::mem::size_of::<Repr>() {
+        for i in 0..=core::mem::size_of::<InlineString<Repr>>() {
             let original = "a".repeat(i);
             check_roundtrip::<Repr>(&original);
         }
@@ -135,12 +131,15 @@ mod tests {
     fn roundtrip_tests() {
         check_roundtrip_repr::<<u8 as ValidLength>::InlineStrRepr>();
         check_roundtrip_repr::<<u16 as ValidLength>::InlineStrRepr>();
+        check_roundtrip_repr::<<crate::U24 as ValidLength>::InlineStrRepr>();
         check_roundtrip_repr::<<u32 as ValidLength>::InlineStrRepr>();
+        check_roundtrip_repr::<<u64 as ValidLength>::InlineStrRepr>();
+        check_roundtrip_repr::<<usize as ValidLength>::InlineStrRepr>();
     }
 
     #[test]
     #[should_panic(expected = "should not overflow")]
     fn check_overflow() {
-        check_roundtrip::<[u8; 8]>("012345678");
+        check_roundtrip::<[u8; 8]>("0123456789");
     }
 }
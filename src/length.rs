@@ -1,106 +1,37 @@
 use core::{
     fmt::{Debug, Display},
-    num::{NonZeroU16, NonZeroU32, NonZeroU8},
+    mem::size_of,
+    num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, TryFromIntError},
 };
 
-use alloc::boxed::Box;
-
-use crate::inline::get_heap_threshold;
+use crate::{array::get_array_size, error::TryFromBoundedError, inline::get_heap_threshold};
 
 mod sealed {
-    use core::num::{NonZeroU16, NonZeroU32, NonZeroU8};
+    use core::num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
 
     pub trait LengthSealed {}
     impl LengthSealed for u8 {}
     impl LengthSealed for u16 {}
     #[cfg(any(target_pointer_width = "64", target_pointer_width = "32"))]
+    impl LengthSealed for super::U24 {}
+    #[cfg(any(target_pointer_width = "64", target_pointer_width = "32"))]
     impl LengthSealed for u32 {}
+    #[cfg(target_pointer_width = "64")]
+    impl LengthSealed for u64 {}
+    impl LengthSealed for usize {}
+    impl<Int: super::ValidLength, const MAX: usize> LengthSealed for super::Bounded<Int, MAX> {}
 
     pub trait NonZeroSealed {}
     impl NonZeroSealed for NonZeroU8 {}
     impl NonZeroSealed for NonZeroU16 {}
     #[cfg(any(target_pointer_width = "64", target_pointer_width = "32"))]
+    impl NonZeroSealed for super::NonZeroU24 {}
+    #[cfg(any(target_pointer_width = "64", target_pointer_width = "32"))]
     impl NonZeroSealed for NonZeroU32 {}
-}
-
-#[derive(Debug)]
-pub struct InvalidLength<T> {
-    type_name: &'static str,
-    original: Box<[T]>,
-}
-
-impl<T> InvalidLength<T> {
-    #[cold]
-    #[track_caller]
-    pub(crate) fn new(type_name: &'static str, original: Box<[T]>) -> Self {
-        Self {
-            type_name,
-            original,
-        }
-    }
-
-    /// Returns the original Box<[T]> that could not be converted from.
-    pub fn get_inner(self) -> Box<[T]> {
-        self.original
-    }
-}
-
-#[cfg(feature = "std")]
-impl<T: Debug> std::error::Error for InvalidLength<T> {}
-
-impl<T> core::fmt::Display for InvalidLength<T> {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(
-            f,
-            "Cannot fit {} into {}",
-            self.original.len(),
-            self.type_name,
-        )
-    }
-}
-
-#[derive(Debug)]
-pub struct InvalidStrLength {
-    type_name: &'static str,
-    original: Box<str>,
-}
-
-impl InvalidStrLength {
-    /// Returns the original [`Box<str>`] that could not be converted from.
-    pub fn get_inner(self) -> Box<str> {
-        self.original
-    }
-}
-
-#[cfg(feature = "std")]
-impl std::error::Error for InvalidStrLength {}
-
-impl core::fmt::Display for InvalidStrLength {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(
-            f,
-            "Cannot fit {} into {}",
-            self.original.len(),
-            self.type_name,
-        )
-    }
-}
-
-impl TryFrom<InvalidLength<u8>> for InvalidStrLength {
-    type Error = core::str::Utf8Error;
-
-    fn try_from(value: InvalidLength<u8>) -> Result<Self, Self::Error> {
-        let original = if let Err(err) = core::str::from_utf8(&value.original) {
-            return Err(err);
-        } else {
-            unsafe { alloc::str::from_boxed_utf8_unchecked(value.original) }
-        };
-
-        Ok(Self {
-            original,
-            type_name: value.type_name,
-        })
-    }
+    #[cfg(target_pointer_width = "64")]
+    impl NonZeroSealed for NonZeroU64 {}
+    impl NonZeroSealed for NonZeroUsize {}
+    impl<Int: super::ValidLength, const MAX: usize> NonZeroSealed for super::NonZeroBounded<Int, MAX> {}
 }
 
 #[doc(hidden)]
@@ -123,32 +54,68 @@ impl NonZero<u16> for NonZeroU16 {
     }
 }
 
+#[cfg(any(target_pointer_width = "64", target_pointer_width = "32"))]
+impl NonZero<U24> for NonZeroU24 {
+    fn new(val: U24) -> Option<Self> {
+        (val != U24::ZERO).then_some(Self(val))
+    }
+}
+
 impl NonZero<u32> for NonZeroU32 {
     fn new(val: u32) -> Option<Self> {
         NonZeroU32::new(val)
     }
 }
 
+#[cfg(target_pointer_width = "64")]
+impl NonZero<u64> for NonZeroU64 {
+    fn new(val: u64) -> Option<Self> {
+        NonZeroU64::new(val)
+    }
+}
+
+impl NonZero<usize> for NonZeroUsize {
+    fn new(val: usize) -> Option<Self> {
+        NonZeroUsize::new(val)
+    }
+}
+
 /// A sealed trait to represent valid lengths for a [`FixedArray`].
 ///
-/// This is implemented on `u32` for non-16 bit platforms, and `u16` on all platforms.
+/// This is implemented on `u8`, `u16` and `usize` on all platforms, [`U24`] and `u32` for non-16 bit platforms,
+/// and `u64` for 64 bit platforms, as well as [`Bounded`] wrappers of each.
 ///
 /// [`FixedArray`]: `crate::array::FixedArray`
 pub trait ValidLength:
-    sealed::LengthSealed + Copy + Display + PartialEq + From<u8> + TryFrom<usize> + Into<u32>
+    sealed::LengthSealed + Copy + Debug + Display + PartialEq + TryFrom<usize>
 {
     const ZERO: Self;
     const MAX: Self;
+    /// [`Self::MAX`] as a [`usize`], for use in constant expressions.
+    #[doc(hidden)]
+    const MAX_USIZE: usize;
+    /// No longer used, as [`FixedArray`] and [`FixedString`] provide a niche in their tag byte for any length.
+    ///
+    /// [`FixedArray`]: crate::FixedArray
+    /// [`FixedString`]: crate::FixedString
     #[deprecated = "will be removed in the next major release"]
     #[allow(deprecated)]
     const DANGLING: Self::NonZero;
 
+    /// No longer used, as [`FixedArray`] and [`FixedString`] provide a niche in their tag byte for any length.
+    ///
+    /// [`FixedArray`]: crate::FixedArray
+    /// [`FixedString`]: crate::FixedString
     #[deprecated = "will be removed in the next major release"]
     type NonZero: NonZero<Self>;
     #[cfg(feature = "typesize")]
     type InlineStrRepr: Copy + AsRef<[u8]> + AsMut<[u8]> + Default + typesize::TypeSize;
     #[cfg(not(feature = "typesize"))]
     type InlineStrRepr: Copy + AsRef<[u8]> + AsMut<[u8]> + Default;
+    /// The bytes of a [`FixedArray`] before its tag byte.
+    ///
+    /// [`FixedArray`]: crate::FixedArray
+    type ArrayRepr: Copy + Default;
 
     #[must_use]
     fn to_usize(self) -> usize;
@@ -162,11 +129,14 @@ pub trait ValidLength:
 impl ValidLength for u8 {
     const ZERO: Self = 0;
     const MAX: Self = Self::MAX;
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    const MAX_USIZE: usize = Self::MAX as usize;
     #[allow(deprecated)]
     const DANGLING: Self::NonZero = Self::NonZero::MAX;
 
     type NonZero = NonZeroU8;
-    type InlineStrRepr = [u8; get_heap_threshold::<Self>()];
+    type InlineStrRepr = [u8; get_heap_threshold::<Self>() - 1];
+    type ArrayRepr = [u8; get_array_size::<Self>() - 1];
 
     fn to_usize(self) -> usize {
         self.into()
@@ -176,11 +146,14 @@ impl ValidLength for u8 {
 impl ValidLength for u16 {
     const ZERO: Self = 0;
     const MAX: Self = Self::MAX;
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    const MAX_USIZE: usize = Self::MAX as usize;
     #[allow(deprecated)]
     const DANGLING: Self::NonZero = Self::NonZero::MAX;
 
     type NonZero = NonZeroU16;
-    type InlineStrRepr = [u8; get_heap_threshold::<Self>()];
+    type InlineStrRepr = [u8; get_heap_threshold::<Self>() - 1];
+    type ArrayRepr = [u8; get_array_size::<Self>() - 1];
 
     fn to_usize(self) -> usize {
         self.into()
@@ -191,11 +164,14 @@ impl ValidLength for u16 {
 impl ValidLength for u32 {
     const ZERO: Self = 0;
     const MAX: Self = Self::MAX;
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    const MAX_USIZE: usize = Self::MAX as usize;
     #[allow(deprecated)]
     const DANGLING: Self::NonZero = Self::NonZero::MAX;
 
     type NonZero = NonZeroU32;
-    type InlineStrRepr = [u8; get_heap_threshold::<Self>()];
+    type InlineStrRepr = [u8; get_heap_threshold::<Self>() - 1];
+    type ArrayRepr = [u8; get_array_size::<Self>() - 1];
 
     fn to_usize(self) -> usize {
         self.try_into()
@@ -203,7 +179,361 @@ impl ValidLength for u32 {
     }
 }
 
+#[cfg(any(target_pointer_width = "64", target_pointer_width = "32"))]
+impl ValidLength for U24 {
+    const ZERO: Self = Self([0; 3]);
+    const MAX: Self = Self([u8::MAX; 3]);
+    const MAX_USIZE: usize = 0xFF_FFFF;
+    #[allow(deprecated)]
+    const DANGLING: Self::NonZero = NonZeroU24(Self::MAX);
+
+    type NonZero = NonZeroU24;
+    type InlineStrRepr = [u8; get_heap_threshold::<Self>() - 1];
+    type ArrayRepr = [u8; get_array_size::<Self>() - 1];
+
+    fn to_usize(self) -> usize {
+        u32::from(self).to_usize()
+    }
+}
+
+#[cfg(target_pointer_width = "64")]
+impl ValidLength for u64 {
+    const ZERO: Self = 0;
+    const MAX: Self = Self::MAX;
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    const MAX_USIZE: usize = Self::MAX as usize;
+    #[allow(deprecated)]
+    const DANGLING: Self::NonZero = Self::NonZero::MAX;
+
+    type NonZero = NonZeroU64;
+    type InlineStrRepr = [u8; get_heap_threshold::<Self>() - 1];
+    type ArrayRepr = [u8; get_array_size::<Self>() - 1];
+
+    fn to_usize(self) -> usize {
+        self.try_into()
+            .expect("u64 can fit into usize on platforms with pointer lengths of 64")
+    }
+}
+
+impl ValidLength for usize {
+    const ZERO: Self = 0;
+    const MAX: Self = Self::MAX;
+    const MAX_USIZE: usize = Self::MAX;
+    #[allow(deprecated)]
+    const DANGLING: Self::NonZero = Self::NonZero::MAX;
+
+    type NonZero = NonZeroUsize;
+    type InlineStrRepr = [u8; get_heap_threshold::<Self>() - 1];
+    type ArrayRepr = [u8; get_array_size::<Self>() - 1];
+
+    fn to_usize(self) -> usize {
+        self
+    }
+}
+
+/// A 24 bit unsigned integer, for use as a [`ValidLength`] between [`u16`] and [`u32`].
+///
+/// This is stored as 3 bytes in native endian order, so has an alignment of 1.
+///
+/// [`FixedArray`] and [`FixedString`] only have 20 bits of the length to store beside their pointer, so values of 2^20
+/// (1 MiB) or more move their slice pointer into a [`Box`], costing an extra allocation.
+/// Use [`u32`] if values this long are common.
+///
+/// [`FixedArray`]: crate::FixedArray
+/// [`FixedString`]: crate::FixedString
+/// [`Box`]: alloc::boxed::Box
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct U24([u8; 3]);
+
+impl U24 {
+    /// Creates a [`U24`] from a [`u32`], returning [`None`] if it is above `U24::MAX`.
+    #[must_use]
+    pub fn new(val: u32) -> Option<Self> {
+        (val <= 0xFF_FFFF).then(|| Self::from_u32_truncating(val))
+    }
+
+    const fn from_u32_truncating(val: u32) -> Self {
+        let [a, b, c, d] = val.to_ne_bytes();
+        if cfg!(target_endian = "big") {
+            Self([b, c, d])
+        } else {
+            Self([a, b, c])
+        }
+    }
+
+    const fn to_u32(self) -> u32 {
+        let [a, b, c] = self.0;
+        if cfg!(target_endian = "big") {
+            u32::from_ne_bytes([0, a, b, c])
+        } else {
+            u32::from_ne_bytes([a, b, c, 0])
+        }
+    }
+}
+
+impl PartialOrd for U24 {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for U24 {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        u32::from(*self).cmp(&u32::from(*other))
+    }
+}
+
+impl Debug for U24 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&u32::from(*self), f)
+    }
+}
+
+impl Display for U24 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&u32::from(*self), f)
+    }
+}
+
+impl From<u8> for U24 {
+    fn from(val: u8) -> Self {
+        Self::from_u32_truncating(val.into())
+    }
+}
+
+impl From<u16> for U24 {
+    fn from(val: u16) -> Self {
+        Self::from_u32_truncating(val.into())
+    }
+}
+
+impl From<U24> for u32 {
+    fn from(val: U24) -> Self {
+        val.to_u32()
+    }
+}
+
+impl TryFrom<usize> for U24 {
+    type Error = TryFromIntError;
+
+    fn try_from(val: usize) -> Result<Self, Self::Error> {
+        let val = u32::try_from(val)?;
+        u16::try_from(val >> 8)?;
+
+        Ok(Self::from_u32_truncating(val))
+    }
+}
+
+#[cfg(feature = "typesize")]
+impl typesize::TypeSize for U24 {}
+
+#[doc(hidden)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct NonZeroU24(U24);
+
+impl From<NonZeroU24> for U24 {
+    fn from(val: NonZeroU24) -> Self {
+        val.0
+    }
+}
+
+/// A length type with a maximum of `MAX`, stored as the integer `Int`.
+///
+/// This allows collections to enforce a domain specific limit, such as `FixedString<Bounded<u8, 32>>` for a string of
+/// at most 32 bytes, while taking up the same space as `Int` would.
+///
+/// `Int` should be the smallest [`ValidLength`] that can hold `MAX`, as it cannot be picked automatically on stable Rust.
+/// Using a `MAX` above `Int::MAX` fails to compile once the length is used.
+///
+/// ```compile_fail
+/// use small_fixed_array::Bounded;
+///
+/// // 300 does not fit into a `u8`.
+/// let len = Bounded::<u8, 300>::try_from(5_usize);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[repr(transparent)]
+pub struct Bounded<Int, const MAX: usize>(Int);
+
+impl<Int: Copy, const MAX: usize> Bounded<Int, MAX> {
+    /// Returns the underlying integer.
+    #[must_use]
+    pub fn get(self) -> Int {
+        self.0
+    }
+}
+
+impl<Int: Display, const MAX: usize> Display for Bounded<Int, MAX> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "typesize")]
+impl<Int, const MAX: usize> typesize::TypeSize for Bounded<Int, MAX> {}
+
+#[doc(hidden)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct NonZeroBounded<Int, const MAX: usize>(Bounded<Int, MAX>);
+
+impl<Int, const MAX: usize> From<NonZeroBounded<Int, MAX>> for Bounded<Int, MAX> {
+    fn from(val: NonZeroBounded<Int, MAX>) -> Self {
+        val.0
+    }
+}
+
+impl<Int: ValidLength, const MAX: usize> Bounded<Int, MAX> {
+    /// Fails to compile if `MAX` does not fit into `Int`, once used.
+    const CHECK: () = assert!(
+        MAX <= Int::MAX_USIZE,
+        "MAX does not fit into the integer type of Bounded"
+    );
+}
+
+macro_rules! impl_bounded {
+    ($($(#[$attr:meta])* $int:ty => $from_max:expr;)*) => {$(
+        $(#[$attr])*
+        impl<const MAX: usize> Bounded<$int, MAX> {
+            /// Creates a [`Bounded`] length, returning [`None`] if `val` is above `MAX`.
+            #[must_use]
+            pub fn new(val: $int) -> Option<Self> {
+                let () = Self::CHECK;
+                (val.to_usize() <= MAX).then_some(Self(val))
+            }
+        }
+
+        $(#[$attr])*
+        impl<const MAX: usize> TryFrom<usize> for Bounded<$int, MAX> {
+            type Error = TryFromBoundedError;
+
+            fn try_from(val: usize) -> Result<Self, Self::Error> {
+                let () = Self::CHECK;
+                <$int as ValidLength>::from_usize(val)
+                    .filter(|_| val <= MAX)
+                    .map(Self)
+                    .ok_or(TryFromBoundedError { max: MAX, actual: val })
+            }
+        }
+
+        $(#[$attr])*
+        impl<const MAX: usize> NonZero<Bounded<$int, MAX>> for NonZeroBounded<$int, MAX> {
+            fn new(val: Bounded<$int, MAX>) -> Option<Self> {
+                (val != Bounded::ZERO).then_some(Self(val))
+            }
+        }
+
+        $(#[$attr])*
+        impl<const MAX: usize> ValidLength for Bounded<$int, MAX> {
+            const ZERO: Self = Self(<$int as ValidLength>::ZERO);
+            #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+            const MAX: Self = {
+                let () = Self::CHECK;
+                Self($from_max)
+            };
+            const MAX_USIZE: usize = {
+                let () = Self::CHECK;
+                MAX
+            };
+            #[allow(deprecated)]
+            const DANGLING: Self::NonZero = NonZeroBounded(Self::MAX);
+
+            type NonZero = NonZeroBounded<$int, MAX>;
+            type InlineStrRepr = <$int as ValidLength>::InlineStrRepr;
+            type ArrayRepr = <$int as ValidLength>::ArrayRepr;
+
+            fn to_usize(self) -> usize {
+                let () = Self::CHECK;
+                self.0.to_usize()
+            }
+
+            fn from_usize(len: usize) -> Option<Self> {
+                len.try_into().ok()
+            }
+        }
+    )*};
+}
+
+impl_bounded! {
+    u8 => MAX as u8;
+    u16 => MAX as u16;
+    #[cfg(any(target_pointer_width = "64", target_pointer_width = "32"))]
+    U24 => U24::from_u32_truncating(MAX as u32);
+    #[cfg(any(target_pointer_width = "64", target_pointer_width = "32"))]
+    u32 => MAX as u32;
+    #[cfg(target_pointer_width = "64")]
+    u64 => MAX as u64;
+    usize => MAX;
+}
+
+union UsizeOrLen<LenT: Copy> {
+    int: usize,
+    len: LenT,
+}
+
+/// Converts `len` into `LenT` in a `const` context, returning [`None`] if it is above `LenT::MAX`.
+pub(crate) const fn const_from_usize<LenT: ValidLength>(len: usize) -> Option<LenT> {
+    if len > LenT::MAX_USIZE {
+        return None;
+    }
+
+    // Move the value into the first bytes of the usize, which `LenT` is read from.
+    let shift = if cfg!(target_endian = "big") {
+        (size_of::<usize>() - size_of::<LenT>()) * 8
+    } else {
+        0
+    };
+
+    // SAFETY: Every `ValidLength` is an integer stored in native endian order, and `len` fits into `LenT`.
+    Some(unsafe { UsizeOrLen { int: len << shift }.len })
+}
+
 #[cfg(target_pointer_width = "16")]
 pub type SmallLen = u16;
 #[cfg(not(target_pointer_width = "16"))]
 pub type SmallLen = u32;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check_u24_conversions() {
+        assert_eq!(U24::MAX.to_usize(), (1 << 24) - 1);
+        assert_eq!(U24::try_from((1_usize << 24) - 1), Ok(U24::MAX));
+        assert!(U24::try_from(1_usize << 24).is_err());
+
+        assert_eq!(U24::new(0x12_34_56).map(u32::from), Some(0x12_34_56));
+        assert_eq!(U24::new(1 << 24), None);
+        assert_eq!(U24::from(u16::MAX).to_usize(), usize::from(u16::MAX));
+        assert!(U24::from(0x100_u16) > U24::from(0xFF_u16));
+    }
+
+    #[test]
+    fn check_const_from_usize() {
+        assert_eq!(const_from_usize::<u8>(200), Some(200));
+        assert_eq!(const_from_usize::<u8>(256), None);
+        assert_eq!(const_from_usize::<u16>(0x1234), Some(0x1234));
+        assert_eq!(const_from_usize::<U24>(0x12_34_56), U24::new(0x12_34_56));
+        assert_eq!(const_from_usize::<usize>(usize::MAX), Some(usize::MAX));
+        assert_eq!(
+            const_from_usize::<Bounded<u16, 300>>(300),
+            Bounded::<u16, 300>::new(300)
+        );
+        assert_eq!(const_from_usize::<Bounded<u16, 300>>(301), None);
+    }
+
+    #[test]
+    fn check_bounded_conversions() {
+        type Len = Bounded<u8, 100>;
+        type U24Len = Bounded<U24, 100_000>;
+
+        assert_eq!(Len::MAX.to_usize(), 100);
+        assert_eq!(Len::from_usize(100).map(Bounded::get), Some(100));
+        assert_eq!(Len::from_usize(101), None);
+        assert_eq!(Len::new(101), None);
+        assert!(Len::try_from(usize::from(u8::MAX) + 1).is_err());
+
+        assert_eq!(U24Len::MAX.to_usize(), 100_000);
+        assert_eq!(U24Len::from_usize(100_001), None);
+    }
+}
@@ -1,5 +1,6 @@
 //! A crate for [`FixedArray`] and [`FixedString`], types to provide a smaller memory footprint in exchange for:
-//! - Immutablity, [`FixedArray`] and [`FixedString`] cannot be mutated without converting back to their expanded forms.
+//! - Immutablity, [`FixedArray`] and [`FixedString`] cannot be mutated without converting back to their expanded forms,
+//!   which `modify` and `modify_trunc` do for you.
 //! - Maximum length, [`FixedArray`] and [`FixedString`] have a length cap of `LenT::MAX` elements.
 //!
 //! These types provide cheap conversions to [`Vec`] and [`String`], to make up for most of these downsides, but it is
@@ -7,16 +8,27 @@
 //!
 //! These can be thought of as `Box<[T]>` and `Box<str>`, except the length is denoted as `LenT`, by default [`u32`].
 //!
+//! [`SharedFixedArray`] and [`SharedFixedString`] are the reference counted equivalents, similar to `Arc<[T]>` and `Arc<str>`,
+//! which can be cloned without copying the data.
+//!
+//! [`ThinFixedArray`] and [`ThinFixedString`] store their length inside the allocation instead, so are the size of a
+//! single pointer at the cost of reading the length from the heap.
+//!
+//! [`UnitString`], most commonly used as [`CharString`] or [`Utf16String`], limits a [`FixedString`] by chars or UTF-16
+//! code units instead of bytes.
+//!
+//! Conversions that can fail return the errors in [`error`], which can be summarised as an [`error::LengthError`].
+//!
 //! ## Features
-//! - `nightly`: Speeds up [`FixedString::len`] for small strings, using `portable_simd`.
 //! - `serde`: Provides [`serde`] implementations for [`FixedArray`] and [`FixedString`].
 //! - `typesize`: Provides [`typesize`] implementations for [`FixedArray`] and [`FixedString`].
+//! - `unicode-segmentation`: Provides `TruncateAt::Grapheme`, to truncate strings without splitting grapheme clusters.
+//! - `strict-truncation`: Panics whenever a value is truncated in a debug build, so tests catch misconfigured limits.
 //!
 //! ## MSRV
 //! The Minimum Supported Rust Version of this crate is 1.70.
 //!
 //! It is considered a breaking change to raise this.
-#![cfg_attr(feature = "nightly", feature(portable_simd))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(feature = "std", allow(unused_imports))]
@@ -26,13 +38,28 @@
 extern crate alloc;
 
 mod array;
+pub mod error;
 mod inline;
+mod last_byte;
 mod length;
-mod r#static;
+mod macros;
+mod repr;
+mod shared;
 mod string;
+mod thin;
 mod truncating_into;
+mod unit;
 
-pub use array::FixedArray;
-pub use length::ValidLength;
+pub use array::{FixedArray, Zeroable};
+pub use length::{Bounded, ValidLength, U24};
+#[doc(hidden)]
+pub use macros::private as __private;
+pub use repr::StorageKind;
+pub use shared::{SharedFixedArray, SharedFixedString};
 pub use string::FixedString;
-pub use truncating_into::TruncatingInto;
+pub use thin::{ThinFixedArray, ThinFixedString};
+pub use truncating_into::{
+    set_truncation_hook, take_truncation_hook, TruncateAt, TruncatingInto, Truncation,
+    TruncationEvent,
+};
+pub use unit::{CharString, Chars, LengthUnit, UnitString, Utf16, Utf16String};
@@ -0,0 +1,199 @@
+/// Creates a [`FixedArray`] containing the arguments, in the same way as [`vec!`].
+///
+/// - `fixed_array![a, b, c]` creates an array of the given elements.
+/// - `fixed_array![x; N]` creates an array of `N` clones of `x`, where `N` must be a constant.
+///
+/// Both forms fail to compile if the length is larger than `LenT::MAX`.
+///
+/// [`FixedArray`]: crate::FixedArray
+/// [`vec!`]: alloc::vec!
+#[macro_export]
+macro_rules! fixed_array {
+    () => {
+        $crate::FixedArray::new()
+    };
+    ($elem:expr; $len:expr) => {
+        $crate::__private::repeat::<_, _, { $len }>($elem)
+    };
+    ($($elem:expr),+ $(,)?) => {
+        $crate::FixedArray::from([$($elem),+])
+    };
+}
+
+/// Creates a [`FixedString`] from a string literal, or from format arguments.
+///
+/// - `fixed_string!("literal")` creates a static [`FixedString`] without allocating or copying, and fails to compile
+///   if the literal is longer than `LenT::MAX`, or too long to store in the tag as in
+///   [`FixedString::const_from_static_trunc`]. This form can be used in `const` and `static` items.
+///   Literals containing `{` or `}` fail to compile, as they would be ambiguous with format strings, so use
+///   `fixed_string!("{}", "{braces}")` instead.
+/// - `fixed_string!("{}: {}", a, b)` formats the arguments as [`format!`] would, without allocating if the result
+///   fits inline.
+///
+/// # Panics
+/// The format form panics if the formatted string is longer than `LenT::MAX`.
+///
+/// ```compile_fail
+/// use small_fixed_array::{fixed_string, FixedString};
+///
+/// let world = "world";
+/// let string: FixedString = fixed_string!("hello {world}");
+/// ```
+///
+/// [`FixedString`]: crate::FixedString
+/// [`FixedString::const_from_static_trunc`]: crate::FixedString::const_from_static_trunc
+/// [`format!`]: alloc::format!
+#[macro_export]
+macro_rules! fixed_string {
+    ($val:literal $(,)?) => {{
+        const VALUE: &'static str = $val;
+        const _: () = ::core::assert!(
+            !$crate::__private::has_format_braces(VALUE),
+            "fixed_string! literals cannot contain braces, use the format form instead"
+        );
+        $crate::__private::static_str::<_, { VALUE.len() }>(VALUE)
+    }};
+    ($fmt:literal, $($args:tt)+) => {
+        $crate::__private::format(::core::format_args!($fmt, $($args)+))
+    };
+}
+
+#[doc(hidden)]
+pub mod private {
+    use alloc::string::String;
+    use core::{fmt::Write, str::FromStr};
+
+    use crate::{
+        array::{AssertFits, AssertStaticFits},
+        FixedArray, FixedString, ValidLength,
+    };
+
+    #[must_use]
+    pub fn repeat<T: Clone, LenT: ValidLength, const N: usize>(value: T) -> FixedArray<T, LenT> {
+        let () = AssertFits::<LenT, N>::CHECK;
+
+        let len = LenT::from_usize(N).unwrap_or_else(|| unreachable!("N has been checked to fit"));
+        FixedArray::repeat(value, len)
+    }
+
+    #[must_use]
+    pub const fn static_str<LenT: ValidLength, const N: usize>(
+        value: &'static str,
+    ) -> FixedString<LenT> {
+        let () = AssertFits::<LenT, N>::CHECK;
+        let () = AssertStaticFits::<LenT, N>::CHECK;
+
+        debug_assert!(value.len() == N);
+        FixedString::const_from_static_trunc(value)
+    }
+
+    #[must_use]
+    pub const fn has_format_braces(value: &str) -> bool {
+        let bytes = value.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if matches!(bytes[i], b'{' | b'}') {
+                return true;
+            }
+
+            i += 1;
+        }
+
+        false
+    }
+
+    /// Writes to a stack buffer large enough for any inline string, then moves to a [`String`] if it overflows.
+    enum FormatBuf {
+        Stack([u8; 16], usize),
+        Heap(String),
+    }
+
+    impl Write for FormatBuf {
+        fn write_str(&mut self, val: &str) -> core::fmt::Result {
+            match self {
+                Self::Stack(buf, len) => {
+                    if let Some(dest) = buf.get_mut(*len..*len + val.len()) {
+                        dest.copy_from_slice(val.as_bytes());
+                        *len += val.len();
+                    } else {
+                        let mut string = String::with_capacity(*len + val.len());
+                        string.push_str(buf_str(buf, *len));
+                        string.push_str(val);
+                        *self = Self::Heap(string);
+                    }
+                }
+                Self::Heap(string) => string.push_str(val),
+            }
+
+            Ok(())
+        }
+    }
+
+    fn buf_str(buf: &[u8], len: usize) -> &str {
+        core::str::from_utf8(&buf[..len])
+            .unwrap_or_else(|_| unreachable!("only whole strs are written"))
+    }
+
+    #[must_use]
+    pub fn format<LenT: ValidLength>(args: core::fmt::Arguments<'_>) -> FixedString<LenT> {
+        if let Some(value) = args.as_str() {
+            return FixedString::from_str(value).unwrap_or_else(|err| panic!("{err}"));
+        }
+
+        let mut buf = FormatBuf::Stack([0; 16], 0);
+        buf.write_fmt(args)
+            .expect("a formatting trait implementation returned an error");
+
+        let result = match buf {
+            FormatBuf::Stack(buf, len) => FixedString::from_str(buf_str(&buf, len)),
+            FormatBuf::Heap(string) => FixedString::try_from(string),
+        };
+
+        result.unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Bounded, FixedArray, FixedString};
+
+    #[test]
+    fn check_fixed_array() {
+        let empty: FixedArray<u8> = fixed_array![];
+        assert!(empty.is_empty());
+
+        let array: FixedArray<u16, u8> = fixed_array![1, 2, 3];
+        assert_eq!(array.as_slice(), &[1, 2, 3]);
+
+        let repeated: FixedArray<&str, Bounded<u8, 4>> = fixed_array!["a"; 4];
+        assert_eq!(repeated.as_slice(), &["a"; 4]);
+    }
+
+    #[test]
+    fn check_fixed_string() {
+        static STATIC: FixedString<u16> = fixed_string!("hello");
+        assert_eq!(STATIC.as_str(), "hello");
+
+        let string: FixedString<u8> = fixed_string!("hello world");
+        assert!(string.is_static());
+        assert_eq!(string, "hello world");
+
+        let braces: FixedString<u8> = fixed_string!("{}", "hello {world}");
+        assert_eq!(braces, "hello {world}");
+
+        let world = "world";
+        let formatted: FixedString<u8> = fixed_string!("hi {}", world);
+        assert!(formatted.is_inline());
+        assert_eq!(formatted, "hi world");
+
+        let formatted: FixedString<u8> = fixed_string!("{world}: {}", "a".repeat(20));
+        assert!(!formatted.is_inline());
+        assert_eq!(formatted.len(), 27);
+    }
+
+    #[test]
+    #[should_panic = "Cannot fit 5 into"]
+    fn check_fixed_string_overflow() {
+        let _: FixedString<Bounded<u8, 4>> = fixed_string!("{}", "hello");
+    }
+}
@@ -0,0 +1,214 @@
+use core::{mem::ManuallyDrop, ptr::NonNull};
+
+use crate::{
+    array::FixedArray, inline::InlineString, last_byte::LastByte, length::const_from_usize,
+    ValidLength,
+};
+
+/// An owned [`FixedStringRepr`], unpacked into a normal enum to be matched on.
+pub(crate) enum UnpackedRepr<LenT: ValidLength> {
+    Array(FixedArray<u8, LenT>),
+    Inline(InlineString<LenT::InlineStrRepr>),
+}
+
+/// Where the contents of a [`FixedArray`] or [`FixedString`] are stored, as returned by `storage_kind`.
+///
+/// [`FixedString`]: crate::FixedString
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StorageKind {
+    /// Borrowed from a `&'static` slice or string, without allocating.
+    Static,
+    /// Allocated on the heap.
+    Heap,
+    /// Stored in the bytes that would otherwise hold the pointer and length, without allocating.
+    Inline,
+}
+
+#[derive(Clone, Copy)]
+#[repr(C, packed)]
+pub(crate) union ReprBody<StrRepr: Copy> {
+    pub(crate) inline: StrRepr,
+    pub(crate) ptr: NonNull<u8>,
+}
+
+/// The storage for [`FixedString`], packed into the same bytes as an [`InlineString`].
+///
+/// Inline strings fill the body, and the last byte as well if they are long enough.
+///
+/// Otherwise this holds a [`FixedArray<u8>`]. For `LenT`s of 3 bytes or more, this has the same layout and always has
+/// a last byte at or above [`LastByte::HEAP`]. Smaller `LenT`s have a [`FixedArray<u8>`] one byte smaller than this,
+/// so it is followed by a last byte of [`LastByte::HEAP`].
+///
+/// [`FixedString`]: crate::FixedString
+#[repr(C)]
+pub(crate) struct FixedStringRepr<LenT: ValidLength> {
+    body: ReprBody<LenT::InlineStrRepr>,
+    last: LastByte,
+}
+
+impl<LenT: ValidLength> FixedStringRepr<LenT> {
+    pub fn from_inline(inline: InlineString<LenT::InlineStrRepr>) -> Self {
+        let (arr, last) = inline.into_parts();
+        Self {
+            body: ReprBody { inline: arr },
+            last,
+        }
+    }
+
+    pub const fn from_array(array: FixedArray<u8, LenT>) -> Self {
+        let array = ArrayOrRepr {
+            array: ManuallyDrop::new(TaggedArray {
+                array: ManuallyDrop::new(array),
+                last: LastByte::HEAP,
+            }),
+        };
+
+        // SAFETY: `FixedArray<u8>` is `repr(C)` and fills `Self`, or all but the last byte which is then set to
+        // `LastByte::HEAP`. Ownership is moved.
+        ManuallyDrop::into_inner(unsafe { array.repr })
+    }
+
+    const fn is_inline(&self) -> bool {
+        self.last.get() < LastByte::HEAP.get()
+    }
+
+    const fn as_inline(&self) -> &InlineString<LenT::InlineStrRepr> {
+        // SAFETY: `InlineString` is `repr(C)` with the same layout as `Self`.
+        unsafe { &*core::ptr::addr_of!(*self).cast() }
+    }
+
+    /// # Safety
+    /// `self` must not be inline.
+    const unsafe fn as_array(&self) -> &FixedArray<u8, LenT> {
+        // SAFETY: `FixedArray<u8>` is `repr(C)` and starts at the start of `Self`, and the tag is checked.
+        unsafe { &*core::ptr::addr_of!(*self).cast() }
+    }
+
+    pub const fn kind(&self) -> StorageKind {
+        if self.is_inline() {
+            StorageKind::Inline
+        } else {
+            // SAFETY: The tag has been checked.
+            unsafe { self.as_array() }.storage_kind()
+        }
+    }
+
+    pub const fn heap_bytes(&self) -> usize {
+        if self.is_inline() {
+            0
+        } else {
+            // SAFETY: The tag has been checked.
+            unsafe { self.as_array() }.heap_bytes()
+        }
+    }
+
+    #[allow(clippy::as_conversions)]
+    pub const fn len(&self) -> LenT {
+        if self.is_inline() {
+            match const_from_usize(self.as_inline().len() as usize) {
+                Some(len) => len,
+                // Inline strings are checked against `LenT::MAX`.
+                None => unreachable!(),
+            }
+        } else {
+            // SAFETY: The tag has been checked.
+            unsafe { self.as_array() }.len()
+        }
+    }
+
+    pub const fn as_str(&self) -> &str {
+        if self.is_inline() {
+            self.as_inline().as_str()
+        } else {
+            // SAFETY: The tag has been checked, and `FixedString` holds the type invariant that the array is UTF-8.
+            unsafe { core::str::from_utf8_unchecked(self.as_array().as_slice()) }
+        }
+    }
+
+    /// Returns the `&'static str` this was created from, if it has not been copied.
+    pub fn as_static_str(&self) -> Option<&'static str> {
+        if self.is_inline() {
+            return None;
+        }
+
+        // SAFETY: The tag has been checked.
+        let bytes = unsafe { self.as_array() }.as_static()?;
+
+        // SAFETY: `FixedString` holds the type invariant that the array is UTF-8.
+        Some(unsafe { core::str::from_utf8_unchecked(bytes) })
+    }
+
+    /// Returns the bytes of a heap allocated string, if this is one.
+    ///
+    /// # Safety
+    /// The bytes must be left as valid UTF-8.
+    pub unsafe fn heap_bytes_mut(&mut self) -> Option<&mut [u8]> {
+        if self.kind() != StorageKind::Heap {
+            return None;
+        }
+
+        // SAFETY: `FixedArray<u8>` is `repr(C)` and starts at the start of `Self`, and the tag is checked.
+        let array = unsafe { &mut *core::ptr::addr_of_mut!(*self).cast::<FixedArray<u8, LenT>>() };
+        Some(array.as_slice_mut())
+    }
+
+    pub fn into_unpacked(self) -> UnpackedRepr<LenT> {
+        let this = ManuallyDrop::new(self);
+        if this.is_inline() {
+            UnpackedRepr::Inline(*this.as_inline())
+        } else {
+            // SAFETY: The tag has been checked, and `this` is never used again.
+            UnpackedRepr::Array(unsafe { core::ptr::read(this.as_array()) })
+        }
+    }
+}
+
+/// A [`FixedArray<u8>`] followed by the last byte of a [`FixedStringRepr`], which is only part of the
+/// [`FixedStringRepr`] if the array is smaller than it.
+#[repr(C)]
+struct TaggedArray<LenT: ValidLength> {
+    array: ManuallyDrop<FixedArray<u8, LenT>>,
+    last: LastByte,
+}
+
+/// Used to move a [`FixedArray<u8>`] into a [`FixedStringRepr`] in a `const` context.
+union ArrayOrRepr<LenT: ValidLength> {
+    array: ManuallyDrop<TaggedArray<LenT>>,
+    repr: ManuallyDrop<FixedStringRepr<LenT>>,
+}
+
+impl<LenT: ValidLength> Clone for FixedStringRepr<LenT> {
+    fn clone(&self) -> Self {
+        if self.is_inline() {
+            Self {
+                body: self.body,
+                last: self.last,
+            }
+        } else {
+            // SAFETY: The tag has been checked.
+            Self::from_array(unsafe { self.as_array() }.clone())
+        }
+    }
+}
+
+impl<LenT: ValidLength> Drop for FixedStringRepr<LenT> {
+    fn drop(&mut self) {
+        // SAFETY: `self` is never used again, as we are in the drop impl.
+        drop(unsafe { core::ptr::read(self) }.into_unpacked());
+    }
+}
+
+unsafe impl<LenT: ValidLength> Send for FixedStringRepr<LenT> {}
+unsafe impl<LenT: ValidLength> Sync for FixedStringRepr<LenT> {}
+
+#[cfg(feature = "typesize")]
+impl<LenT: ValidLength> typesize::TypeSize for FixedStringRepr<LenT> {
+    fn extra_size(&self) -> usize {
+        if self.is_inline() {
+            0
+        } else {
+            // SAFETY: The tag has been checked.
+            unsafe { self.as_array() }.extra_size()
+        }
+    }
+}
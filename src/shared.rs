@@ -0,0 +1,542 @@
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use core::{borrow::Borrow, fmt::Debug, hash::Hash, mem::ManuallyDrop, ptr::NonNull};
+
+use crate::{
+    error::InvalidSharedLength,
+    length::{SmallLen, ValidLength},
+    FixedArray, FixedString,
+};
+
+/// A reference counted [`FixedArray`], which can be cloned in O(1).
+///
+/// This can be thought of as `Arc<[T]>`, except the length is denoted as `LenT`, by default [`u32`].
+///
+/// Empty arrays do not hold an [`Arc`], so can be created without allocating.
+#[repr(packed)]
+pub struct SharedFixedArray<T, LenT: ValidLength = SmallLen> {
+    ptr: NonNull<T>,
+    len: LenT,
+}
+
+impl<T, LenT: ValidLength> SharedFixedArray<T, LenT> {
+    /// Creates an empty [`SharedFixedArray`], without allocating.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            ptr: NonNull::dangling(),
+            len: LenT::ZERO,
+        }
+    }
+
+    /// # Safety
+    /// - `len` must be equal to `arc.len()`
+    unsafe fn from_arc(arc: Arc<[T]>, len: LenT) -> Self {
+        debug_assert_eq!(len.to_usize(), arc.len());
+        if arc.is_empty() {
+            return Self::new();
+        }
+
+        let ptr = Arc::into_raw(arc).cast::<T>().cast_mut();
+        Self {
+            ptr: NonNull::new(ptr).expect("Arc ptr != nullptr"),
+            len,
+        }
+    }
+
+    /// Converts the [`SharedFixedArray`] to it's original [`Arc<[T]>`], without touching the reference count.
+    ///
+    /// # Safety
+    /// `self` must not be empty, and must never be used again. It is highly recommended to wrap in [`ManuallyDrop`]
+    /// before calling.
+    unsafe fn as_arc(&self) -> Arc<[T]> {
+        let slice =
+            core::ptr::slice_from_raw_parts(self.ptr.as_ptr().cast_const(), self.len().to_usize());
+
+        // SAFETY: `self` has been derived from `Arc<[T]>`
+        unsafe { Arc::from_raw(slice) }
+    }
+
+    /// Returns the length of the [`SharedFixedArray`].
+    #[must_use]
+    pub fn len(&self) -> LenT {
+        self.len
+    }
+
+    /// Returns if the length is equal to 0.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == LenT::ZERO
+    }
+
+    /// Converts `&`[`SharedFixedArray<T>`] to `&[T]`, this conversion can be performed by [`core::ops::Deref`].
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    /// Converts [`SharedFixedArray<T>`] to [`Arc<[T]>`], this operation does not allocate unless the array is empty.
+    #[must_use]
+    pub fn into_arc(self) -> Arc<[T]> {
+        self.into()
+    }
+
+    /// Returns the amount of [`SharedFixedArray`]s and [`Arc`]s pointing to this allocation.
+    ///
+    /// Empty arrays do not share an allocation, so always return 1.
+    #[must_use]
+    pub fn strong_count(this: &Self) -> usize {
+        if this.is_empty() {
+            return 1;
+        }
+
+        // SAFETY: The array is not empty, and the Arc is never dropped, so the reference count is not changed.
+        Arc::strong_count(&ManuallyDrop::new(unsafe { this.as_arc() }))
+    }
+
+    /// Returns if both [`SharedFixedArray`]s point to the same allocation.
+    #[must_use]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        ({ this.ptr }) == { other.ptr } && this.len() == other.len()
+    }
+}
+
+unsafe impl<T: Send + Sync, LenT: ValidLength> Send for SharedFixedArray<T, LenT> {}
+unsafe impl<T: Send + Sync, LenT: ValidLength> Sync for SharedFixedArray<T, LenT> {}
+
+impl<T, LenT: ValidLength> core::ops::Deref for SharedFixedArray<T, LenT> {
+    type Target = [T];
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: `self.ptr` and `self.len` are both valid and derived from `Arc<[T]>`.
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len().to_usize()) }
+    }
+}
+
+impl<T, LenT: ValidLength> Drop for SharedFixedArray<T, LenT> {
+    fn drop(&mut self) {
+        if !self.is_empty() {
+            // SAFETY: The array is not empty, we never use `self` again, and we are in the drop impl.
+            drop(unsafe { self.as_arc() });
+        }
+    }
+}
+
+impl<T, LenT: ValidLength> Clone for SharedFixedArray<T, LenT> {
+    fn clone(&self) -> Self {
+        if self.is_empty() {
+            return Self::new();
+        }
+
+        // SAFETY: The array is not empty, and the original Arc is never dropped, so only the clone increments the
+        // reference count.
+        let arc = Arc::clone(&ManuallyDrop::new(unsafe { self.as_arc() }));
+
+        // SAFETY: The clone points to the same slice, so the length cannot mismatch.
+        unsafe { Self::from_arc(arc, self.len) }
+    }
+}
+
+impl<T, LenT: ValidLength> Default for SharedFixedArray<T, LenT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Hash, LenT: ValidLength> Hash for SharedFixedArray<T, LenT> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl<T: PartialEq, LenT: ValidLength> PartialEq for SharedFixedArray<T, LenT> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+impl<T: Eq, LenT: ValidLength> Eq for SharedFixedArray<T, LenT> {}
+
+impl<T: PartialOrd, LenT: ValidLength> PartialOrd for SharedFixedArray<T, LenT> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: Ord, LenT: ValidLength> Ord for SharedFixedArray<T, LenT> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl<T: Debug, LenT: ValidLength> Debug for SharedFixedArray<T, LenT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        <[T] as Debug>::fmt(self, f)
+    }
+}
+
+impl<'a, T, LenT: ValidLength> IntoIterator for &'a SharedFixedArray<T, LenT> {
+    type Item = <&'a [T] as IntoIterator>::Item;
+    type IntoIter = <&'a [T] as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<T, LenT: ValidLength> AsRef<[T]> for SharedFixedArray<T, LenT> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, LenT: ValidLength> From<SharedFixedArray<T, LenT>> for Arc<[T]> {
+    fn from(value: SharedFixedArray<T, LenT>) -> Self {
+        if value.is_empty() {
+            return Arc::from(Vec::new());
+        }
+
+        let value = ManuallyDrop::new(value);
+
+        // SAFETY: The array is not empty, and we don't use value again as it is ManuallyDrop.
+        unsafe { value.as_arc() }
+    }
+}
+
+/// Moves the elements into a new [`Arc<[T]>`] allocation, as the reference counts are stored before the data.
+impl<T, LenT: ValidLength> From<FixedArray<T, LenT>> for SharedFixedArray<T, LenT> {
+    fn from(value: FixedArray<T, LenT>) -> Self {
+        let len = value.len();
+
+        // SAFETY: The length is taken from the same array.
+        unsafe { Self::from_arc(Arc::from(value), len) }
+    }
+}
+
+impl<T, LenT: ValidLength> TryFrom<Arc<[T]>> for SharedFixedArray<T, LenT> {
+    type Error = InvalidSharedLength<[T], LenT>;
+    fn try_from(arc: Arc<[T]>) -> Result<Self, Self::Error> {
+        let Some(len) = LenT::from_usize(arc.len()) else {
+            return Err(InvalidSharedLength::new(arc));
+        };
+
+        // SAFETY: `len` was derived from the arc length.
+        Ok(unsafe { Self::from_arc(arc, len) })
+    }
+}
+
+impl<T, LenT: ValidLength> TryFrom<Box<[T]>> for SharedFixedArray<T, LenT> {
+    type Error = InvalidSharedLength<[T], LenT>;
+    fn try_from(boxed_array: Box<[T]>) -> Result<Self, Self::Error> {
+        Arc::<[T]>::from(boxed_array).try_into()
+    }
+}
+
+impl<T, LenT: ValidLength> TryFrom<Vec<T>> for SharedFixedArray<T, LenT> {
+    type Error = InvalidSharedLength<[T], LenT>;
+    fn try_from(vec: Vec<T>) -> Result<Self, Self::Error> {
+        Arc::<[T]>::from(vec).try_into()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, LenT> serde::Deserialize<'de> for SharedFixedArray<T, LenT>
+where
+    T: serde::Deserialize<'de>,
+    LenT: ValidLength,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::try_from(Box::<[T]>::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T, LenT> serde::Serialize for SharedFixedArray<T, LenT>
+where
+    T: serde::Serialize,
+    LenT: ValidLength,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "typesize")]
+impl<T: typesize::TypeSize, LenT: ValidLength> typesize::TypeSize for SharedFixedArray<T, LenT> {
+    fn extra_size(&self) -> usize {
+        self.iter().map(T::get_size).sum()
+    }
+}
+
+/// A reference counted [`FixedString`], which can be cloned in O(1).
+///
+/// This can be thought of as `Arc<str>`, except the length is denoted as `LenT`, by default [`u32`].
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+pub struct SharedFixedString<LenT: ValidLength = SmallLen>(SharedFixedArray<u8, LenT>);
+
+impl<LenT: ValidLength> SharedFixedString<LenT> {
+    /// Creates an empty [`SharedFixedString`], without allocating.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(SharedFixedArray::new())
+    }
+
+    /// Returns the length of the [`SharedFixedString`].
+    #[must_use]
+    pub fn len(&self) -> LenT {
+        self.0.len()
+    }
+
+    /// Returns if the length is equal to 0.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Converts `&`[`SharedFixedString`] to `&str`, this conversion can be performed by [`core::ops::Deref`].
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self
+    }
+
+    /// Converts [`SharedFixedString`] to [`Arc<str>`], this operation does not allocate unless the string is empty.
+    #[must_use]
+    pub fn into_arc(self) -> Arc<str> {
+        self.into()
+    }
+
+    /// Returns the amount of [`SharedFixedString`]s and [`Arc`]s pointing to this allocation.
+    #[must_use]
+    pub fn strong_count(this: &Self) -> usize {
+        SharedFixedArray::strong_count(&this.0)
+    }
+
+    /// Returns if both [`SharedFixedString`]s point to the same allocation.
+    #[must_use]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        SharedFixedArray::ptr_eq(&this.0, &other.0)
+    }
+}
+
+impl<LenT: ValidLength> core::ops::Deref for SharedFixedString<LenT> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: Self holds the type invariant that the array is UTF-8.
+        unsafe { core::str::from_utf8_unchecked(&self.0) }
+    }
+}
+
+impl<LenT: ValidLength> Default for SharedFixedString<LenT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<LenT: ValidLength> Clone for SharedFixedString<LenT> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<LenT: ValidLength> Hash for SharedFixedString<LenT> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl<LenT: ValidLength> PartialEq for SharedFixedString<LenT> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<LenT: ValidLength> Eq for SharedFixedString<LenT> {}
+
+impl<LenT: ValidLength> PartialEq<str> for SharedFixedString<LenT> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str().eq(other)
+    }
+}
+
+impl<LenT: ValidLength> PartialEq<&str> for SharedFixedString<LenT> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str().eq(*other)
+    }
+}
+
+impl<LenT: ValidLength> core::cmp::PartialOrd for SharedFixedString<LenT> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<LenT: ValidLength> core::cmp::Ord for SharedFixedString<LenT> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<LenT: ValidLength> core::fmt::Display for SharedFixedString<LenT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self)
+    }
+}
+
+impl<LenT: ValidLength> core::fmt::Debug for SharedFixedString<LenT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+impl<LenT: ValidLength> AsRef<str> for SharedFixedString<LenT> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<LenT: ValidLength> Borrow<str> for SharedFixedString<LenT> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<LenT: ValidLength> From<SharedFixedString<LenT>> for Arc<str> {
+    #[allow(clippy::as_conversions)]
+    fn from(value: SharedFixedString<LenT>) -> Self {
+        let bytes = Arc::into_raw(Arc::<[u8]>::from(value.0));
+
+        // SAFETY: Self holds the type invariant that the array is UTF-8, and `str` has the same layout as `[u8]`.
+        unsafe { Arc::from_raw(bytes as *const str) }
+    }
+}
+
+/// Copies the string into a new [`Arc<str>`] allocation, as the reference counts are stored before the data.
+impl<LenT: ValidLength> From<FixedString<LenT>> for SharedFixedString<LenT> {
+    fn from(value: FixedString<LenT>) -> Self {
+        Arc::<str>::from(value)
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("FixedString<LenT> length should fit into LenT"))
+    }
+}
+
+impl<LenT: ValidLength> TryFrom<Arc<str>> for SharedFixedString<LenT> {
+    type Error = InvalidSharedLength<str, LenT>;
+
+    fn try_from(value: Arc<str>) -> Result<Self, Self::Error> {
+        let Some(len) = LenT::from_usize(value.len()) else {
+            return Err(InvalidSharedLength::new(value));
+        };
+
+        // SAFETY: `len` was derived from the arc length.
+        Ok(Self(unsafe {
+            SharedFixedArray::from_arc(value.into(), len)
+        }))
+    }
+}
+
+impl<LenT: ValidLength> TryFrom<Box<str>> for SharedFixedString<LenT> {
+    type Error = InvalidSharedLength<str, LenT>;
+
+    fn try_from(value: Box<str>) -> Result<Self, Self::Error> {
+        Arc::<str>::from(value).try_into()
+    }
+}
+
+impl<LenT: ValidLength> TryFrom<String> for SharedFixedString<LenT> {
+    type Error = InvalidSharedLength<str, LenT>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Arc::<str>::from(value).try_into()
+    }
+}
+
+impl<LenT: ValidLength> core::str::FromStr for SharedFixedString<LenT> {
+    type Err = InvalidSharedLength<str, LenT>;
+
+    fn from_str(val: &str) -> Result<Self, Self::Err> {
+        Arc::<str>::from(val).try_into()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, LenT: ValidLength> serde::Deserialize<'de> for SharedFixedString<LenT> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::try_from(Box::<str>::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<LenT: ValidLength> serde::Serialize for SharedFixedString<LenT> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_str().serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::str::FromStr;
+
+    #[test]
+    fn check_clone_shares_allocation() {
+        let original = SharedFixedString::<u8>::from(FixedString::from_str_trunc(
+            "a string that is too long to be stored inline",
+        ));
+        let cloned = original.clone();
+
+        assert!(SharedFixedString::ptr_eq(&original, &cloned));
+        assert_eq!(SharedFixedString::strong_count(&original), 2);
+
+        drop(cloned);
+        assert_eq!(SharedFixedString::strong_count(&original), 1);
+    }
+
+    #[test]
+    fn check_arc_roundtrip() {
+        let arc = Arc::<str>::from("Hello, world!");
+        let shared = SharedFixedString::<u8>::try_from(Arc::clone(&arc)).unwrap();
+        assert_eq!(shared, "Hello, world!");
+        assert_eq!(Arc::strong_count(&arc), 2);
+
+        let back = Arc::<str>::from(shared);
+        assert!(Arc::ptr_eq(&arc, &back));
+
+        let array = SharedFixedArray::<u32, u8>::try_from(alloc::vec![1, 2, 3]).unwrap();
+        assert_eq!(array.len(), 3);
+        assert_eq!(&*Arc::<[u32]>::from(array.clone()), array.as_slice());
+    }
+
+    #[test]
+    fn check_length_is_checked() {
+        let long = "a".repeat(256);
+        let err = SharedFixedString::<u8>::from_str(&long).unwrap_err();
+        assert_eq!(err.get_inner().len(), 256);
+
+        let err = SharedFixedArray::<u8, u8>::try_from(alloc::vec![0; 256]).unwrap_err();
+        assert_eq!(err.get_inner().len(), 256);
+    }
+
+    #[test]
+    fn check_default() {
+        const EMPTY: SharedFixedString<u8> = SharedFixedString::new();
+
+        let empty = EMPTY.clone();
+        assert!(empty.is_empty());
+        assert_eq!(empty.as_str(), "");
+        assert_eq!(SharedFixedString::strong_count(&empty), 1);
+        assert_eq!(&*Arc::<str>::from(empty), "");
+
+        let from_arc = SharedFixedArray::<u8, u8>::try_from(Arc::<[u8]>::from(Vec::new())).unwrap();
+        assert_eq!(from_arc, SharedFixedArray::default());
+        assert!(SharedFixedArray::<u8, u8>::default().into_arc().is_empty());
+    }
+
+    #[test]
+    fn check_ord() {
+        let a = SharedFixedArray::<u8, u8>::try_from(alloc::vec![1, 2]).unwrap();
+        let b = SharedFixedArray::<u8, u8>::try_from(alloc::vec![1, 3]).unwrap();
+
+        assert!(a < b);
+        assert_eq!(a.cmp(&a.clone()), core::cmp::Ordering::Equal);
+        assert!(SharedFixedArray::new() < a);
+    }
+}
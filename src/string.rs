@@ -4,70 +4,159 @@ use alloc::{
     string::String,
     sync::Arc,
 };
-use core::{borrow::Borrow, hash::Hash, str::FromStr};
+use core::{
+    borrow::Borrow,
+    hash::Hash,
+    ops::{Bound, RangeBounds},
+    ptr::NonNull,
+    str::FromStr,
+};
 
 use crate::{
-    array::FixedArray,
+    array::{checked_len, joined_len, FixedArray, RestoreOnDrop},
+    error::{FromUtf8Error, InvalidStrLength, LengthError},
     inline::InlineString,
-    length::{InvalidStrLength, SmallLen, ValidLength},
-    r#static::StaticStr,
+    length::{SmallLen, ValidLength},
+    repr::{FixedStringRepr, StorageKind, UnpackedRepr},
+    truncating_into::{report_const_truncation, report_truncation, Truncation},
 };
 
-#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
-enum FixedStringRepr<LenT: ValidLength> {
-    Static(StaticStr<LenT>),
-    Heap(FixedArray<u8, LenT>),
-    Inline(InlineString<LenT::InlineStrRepr>),
-}
-
 #[cold]
-fn truncate_string(err: InvalidStrLength, max_len: usize) -> String {
+fn truncate_string<LenT: ValidLength>(err: InvalidStrLength<LenT>, max_len: usize) -> String {
     let mut value = String::from(err.get_inner());
     value.truncate(truncate_str(&value, max_len).len());
     value
 }
 
-#[cold]
-fn truncate_str(string: &str, max_len: usize) -> &str {
-    for len in (0..=max_len).rev() {
-        if string.is_char_boundary(len) {
-            return &string[..len];
+pub(crate) const fn truncate_str(string: &str, max_len: usize) -> &str {
+    let bytes = string.as_bytes();
+    if bytes.len() <= max_len {
+        return string;
+    }
+
+    // Every byte apart from UTF-8 continuation bytes (`0b10xx_xxxx`) starts a char.
+    let mut len = max_len;
+    while bytes[len] & 0xC0 == 0x80 {
+        len -= 1;
+    }
+
+    // SAFETY: `len` is within `string` and is a char boundary.
+    unsafe { core::str::from_utf8_unchecked(core::slice::from_raw_parts(bytes.as_ptr(), len)) }
+}
+
+/// Collects `iter` into a [`String`], stopping once it is at least `stop_len` bytes long.
+fn collect_string<I>(iter: I, stop_len: usize) -> String
+where
+    I: IntoIterator,
+    String: Extend<I::Item>,
+{
+    let iter = iter.into_iter();
+    let mut string = String::with_capacity(iter.size_hint().0.min(stop_len));
+    for item in iter {
+        string.extend(core::iter::once(item));
+        if string.len() >= stop_len {
+            break;
         }
     }
 
-    unreachable!("Len 0 is a char boundary");
+    string
 }
 
 /// A fixed size String with length provided at creation denoted in [`ValidLength`], by default [`u32`].
 ///
 /// See module level documentation for more information.
+///
+/// This has the same niche as [`FixedArray`], so nested options, such as `Option<Option<FixedString>>`, are the
+/// same size as [`FixedString`] itself.
 #[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
 pub struct FixedString<LenT: ValidLength = SmallLen>(FixedStringRepr<LenT>);
 
 impl<LenT: ValidLength> FixedString<LenT> {
     #[must_use]
-    pub fn new() -> Self {
-        Self::from_static_trunc("")
+    pub const fn new() -> Self {
+        Self::const_from_static_trunc("")
     }
 
     pub(crate) fn new_inline(val: &str) -> Option<Self> {
+        LenT::from_usize(val.len())?;
         InlineString::from_str(val)
-            .map(FixedStringRepr::Inline)
+            .map(FixedStringRepr::from_inline)
             .map(Self)
     }
 
+    /// # Safety
+    /// - `array` must be valid UTF-8.
+    pub(crate) unsafe fn from_array_unchecked(array: FixedArray<u8, LenT>) -> Self {
+        Self(FixedStringRepr::from_array(array))
+    }
+
     /// Converts a `&'static str` into a [`FixedString`].
     ///
-    /// This method will not allocate, or copy the string data.
+    /// This method will not copy the string data, and only allocates if the length is too long to store in the tag,
+    /// see [`FixedArray::from_static_trunc`].
     ///
     /// See [`Self::from_string_trunc`] for truncation behaviour.
-    pub fn from_static_trunc(mut val: &'static str) -> Self {
-        let max_len = LenT::MAX.to_usize();
-        if val.len() > max_len {
-            val = truncate_str(val, max_len);
+    #[must_use]
+    #[track_caller]
+    pub fn from_static_trunc(val: &'static str) -> Self {
+        let (string, dropped) = Self::from_static_trunc_report(val);
+        if dropped != 0 {
+            report_truncation::<Self>(val.len(), val.len() - dropped);
         }
 
-        Self(FixedStringRepr::Static(StaticStr::from_static_str(val)))
+        string
+    }
+
+    /// Converts a `&'static str` into a [`FixedString`], as in [`Self::from_static_trunc`], also returning the number
+    /// of bytes that were **truncated**.
+    ///
+    /// Unlike [`Self::from_static_trunc`], this does not call the truncation hook or panic with the
+    /// `strict-truncation` feature, as the caller is told about the truncation.
+    #[must_use]
+    pub fn from_static_trunc_report(val: &'static str) -> (Self, usize) {
+        let prefix = truncate_str(val, LenT::MAX_USIZE);
+        let (array, _) = FixedArray::from_static_trunc_report(prefix.as_bytes());
+
+        // SAFETY: `prefix` is a `&str`, so is valid UTF-8.
+        let string = unsafe { Self::from_array_unchecked(array) };
+        (string, val.len() - prefix.len())
+    }
+
+    /// Converts a `&'static str` into a [`FixedString`] in a `const` context, such as a `const` or `static` item.
+    ///
+    /// See [`FixedArray::const_from_static_trunc`] for truncation behaviour.
+    ///
+    /// # Panics
+    /// See [`FixedArray::const_from_static_trunc`].
+    #[must_use]
+    pub const fn const_from_static_trunc(val: &'static str) -> Self {
+        if val.len() > LenT::MAX_USIZE {
+            report_const_truncation();
+        }
+
+        Self::from_static_prefix(val)
+    }
+
+    /// Converts a `&'static str` into a [`FixedString`], as in [`Self::const_from_static_trunc`], also returning the
+    /// number of bytes that were **truncated**.
+    ///
+    /// Unlike [`Self::const_from_static_trunc`], this does not panic with the `strict-truncation` feature.
+    ///
+    /// # Panics
+    /// See [`FixedArray::const_from_static_trunc`].
+    #[must_use]
+    pub const fn const_from_static_trunc_report(val: &'static str) -> (Self, usize) {
+        let dropped = val.len() - truncate_str(val, LenT::MAX_USIZE).len();
+        (Self::from_static_prefix(val), dropped)
+    }
+
+    /// Converts the longest prefix of a `&'static str` that fits into `LenT::MAX` bytes into a [`FixedString`].
+    const fn from_static_prefix(val: &'static str) -> Self {
+        let val = truncate_str(val, LenT::MAX_USIZE);
+
+        Self(FixedStringRepr::from_array(
+            FixedArray::const_from_static_trunc(val.as_bytes()),
+        ))
     }
 
     /// Converts a `&str` into a [`FixedString`], allocating if the value cannot fit "inline".
@@ -77,11 +166,12 @@ impl<LenT: ValidLength> FixedString<LenT> {
     ///
     /// If the `&str` is `'static`, it is preferred to use [`Self::from_static_trunc`], which does not need to copy the data around.
     ///
-    /// "Inline" refers to Small String Optimisation which allows for Strings with less than 9 to 11 characters
+    /// "Inline" refers to Small String Optimisation which allows for Strings with less than 10 to 12 characters
     /// to be stored without allocation, saving a pointer size and an allocation.
     ///
     /// See [`Self::from_string_trunc`] for truncation behaviour.
     #[must_use]
+    #[track_caller]
     pub fn from_str_trunc(val: &str) -> Self {
         if let Some(inline) = Self::new_inline(val) {
             inline
@@ -90,38 +180,394 @@ impl<LenT: ValidLength> FixedString<LenT> {
         }
     }
 
+    /// Converts a `&str` into a [`FixedString`], as in [`Self::from_str_trunc`], also returning the number of bytes
+    /// that were **truncated**.
+    ///
+    /// See [`Self::from_string_trunc_report`].
+    #[must_use]
+    pub fn from_str_trunc_report(val: &str) -> (Self, usize) {
+        if let Some(inline) = Self::new_inline(val) {
+            (inline, 0)
+        } else {
+            Self::from_string_trunc_report(val.to_owned())
+        }
+    }
+
     /// Converts a [`String`] into a [`FixedString`], **truncating** if the value is larger than `LenT`'s maximum.
     ///
     /// This allows for infallible conversion, but may be lossy in the case of a value above `LenT`'s max.
     /// For lossless fallible conversion, convert to [`Box<str>`] using [`String::into_boxed_str`] and use [`TryFrom`].
+    ///
+    /// Truncation calls the hook set with [`set_truncation_hook`], and panics in debug builds with the
+    /// `strict-truncation` feature.
+    ///
+    /// [`set_truncation_hook`]: crate::set_truncation_hook
     #[must_use]
+    #[track_caller]
     pub fn from_string_trunc(str: String) -> Self {
+        let original_len = str.len();
+        let (string, dropped) = Self::from_string_trunc_report(str);
+        if dropped != 0 {
+            report_truncation::<Self>(original_len, original_len - dropped);
+        }
+
+        string
+    }
+
+    /// Converts a [`String`] into a [`FixedString`], as in [`Self::from_string_trunc`], also returning the number of
+    /// bytes that were **truncated**.
+    ///
+    /// Unlike [`Self::from_string_trunc`], this does not call the truncation hook or panic with the
+    /// `strict-truncation` feature, as the caller is told about the truncation.
+    #[must_use]
+    pub fn from_string_trunc_report(str: String) -> (Self, usize) {
         match str.try_into() {
-            Ok(val) => val,
-            Err(err) => Self::from_string_trunc(truncate_string(err, LenT::MAX.to_usize())),
+            Ok(val) => (val, 0),
+            Err(err) => {
+                let original_len = err.actual();
+                let truncated = Self::try_from(truncate_string(err, LenT::MAX.to_usize()))
+                    .unwrap_or_else(|_| unreachable!("the truncated string should fit into LenT"));
+
+                let dropped = original_len - truncated.len().to_usize();
+                (truncated, dropped)
+            }
         }
     }
 
-    /// Returns the length of the [`FixedString`].
+    /// Converts a `&str` into a [`FixedString`], **truncating** it as described by `truncation` if it is too long.
+    ///
+    /// This allocates at most once, even if a suffix is appended.
     #[must_use]
-    pub fn len(&self) -> LenT {
-        match &self.0 {
-            FixedStringRepr::Heap(a) => a.len(),
-            FixedStringRepr::Static(a) => a.len(),
-            FixedStringRepr::Inline(a) => a.len().into(),
+    #[track_caller]
+    pub fn from_str_trunc_with(val: &str, truncation: Truncation<'_>) -> Self {
+        let Some((prefix, suffix)) = truncation.split(val, LenT::MAX_USIZE) else {
+            return Self::from_str_trunc(val);
+        };
+
+        let len_usize = prefix.len() + suffix.len();
+        report_truncation::<Self>(val.len(), len_usize);
+
+        let len = LenT::from_usize(len_usize)
+            .unwrap_or_else(|| unreachable!("the truncated value should fit into LenT"));
+
+        Self::from_parts(len, |push| {
+            push(prefix);
+            push(suffix);
+        })
+    }
+
+    /// Converts a [`String`] into a [`FixedString`], **truncating** it as described by `truncation` if it is too long.
+    ///
+    /// The allocation of the [`String`] is reused, as in [`Self::from_string_trunc`].
+    #[must_use]
+    #[track_caller]
+    pub fn from_string_trunc_with(mut val: String, truncation: Truncation<'_>) -> Self {
+        if let Some((prefix, suffix)) = truncation.split(&val, LenT::MAX_USIZE) {
+            let prefix_len = prefix.len();
+            report_truncation::<Self>(val.len(), prefix_len + suffix.len());
+
+            val.truncate(prefix_len);
+            val.push_str(suffix);
         }
+
+        Self::from_string_trunc(val)
+    }
+
+    /// Creates a [`FixedString`] from the parts passed to `push` by `for_each_part`, which should total `len` bytes.
+    ///
+    /// This allocates once at the final size, or not at all if the string fits inline.
+    fn from_parts(len: LenT, for_each_part: impl Fn(&mut dyn FnMut(&str))) -> Self {
+        let len_usize = len.to_usize();
+
+        // Parts past `len` are truncated to a char boundary, in case they differ between calls.
+        let for_each_byte_part = |push: &mut dyn FnMut(&[u8])| {
+            let mut remaining = len_usize;
+            for_each_part(&mut |part| {
+                let part = truncate_str(part, remaining);
+                remaining -= part.len();
+                push(part.as_bytes());
+            });
+        };
+
+        let inline = InlineString::from_len_and_write(len_usize, |buf| {
+            let mut pos = 0;
+            for_each_byte_part(&mut |part| {
+                buf[pos..pos + part.len()].copy_from_slice(part);
+                pos += part.len();
+            });
+        });
+
+        if let Some(inline) = inline {
+            return Self(FixedStringRepr::from_inline(inline));
+        }
+
+        let array = FixedArray::from_parts(len, for_each_byte_part);
+
+        // SAFETY: Each part is a whole `str`, or truncated to a char boundary.
+        unsafe { Self::from_array_unchecked(array) }
+    }
+
+    /// Concatenates `parts` into a [`FixedString`].
+    ///
+    /// The length is calculated up front, so this allocates once at the final size, or not at all if the string fits
+    /// inline.
+    ///
+    /// # Errors
+    /// Returns [`LengthError::TooLong`] if the result is longer than `LenT::MAX` bytes.
+    pub fn concat<S: AsRef<str>>(parts: &[S]) -> Result<Self, LengthError<LenT>> {
+        Self::join(parts, "")
+    }
+
+    /// Concatenates `parts` into a [`FixedString`], with `sep` between each part.
+    ///
+    /// The length is calculated up front, so this allocates once at the final size, or not at all if the string fits
+    /// inline.
+    ///
+    /// # Errors
+    /// Returns [`LengthError::TooLong`] if the result is longer than `LenT::MAX` bytes.
+    pub fn join<I>(parts: I, sep: &str) -> Result<Self, LengthError<LenT>>
+    where
+        I: IntoIterator,
+        I::IntoIter: Clone,
+        I::Item: AsRef<str>,
+    {
+        let parts = parts.into_iter();
+        let len = joined_len::<LenT>(parts.clone().map(|part| part.as_ref().len()), sep.len())?;
+
+        Ok(Self::from_parts(len, |push| {
+            for (i, part) in parts.clone().enumerate() {
+                if i != 0 {
+                    push(sep);
+                }
+
+                push(part.as_ref());
+            }
+        }))
+    }
+
+    /// Creates a [`FixedString`] of `self` repeated `n` times, the equivalent of [`str::repeat`].
+    ///
+    /// The length is calculated up front, so this allocates once at the final size, or not at all if the string fits
+    /// inline.
+    ///
+    /// # Errors
+    /// Returns [`LengthError::TooLong`] if the result is longer than `LenT::MAX` bytes.
+    pub fn repeated(&self, n: usize) -> Result<Self, LengthError<LenT>> {
+        if self.is_empty() {
+            return Ok(Self::new());
+        }
+
+        let len = checked_len::<LenT>(self.len().to_usize().saturating_mul(n))?;
+        Ok(Self::from_parts(len, |push| {
+            for _ in 0..n {
+                push(self);
+            }
+        }))
+    }
+
+    /// Creates a [`FixedString`] from `sub`, which must be a substring of `self`.
+    ///
+    /// Static strings are narrowed without copying, otherwise this is stored inline if it fits, or copied into a
+    /// single allocation.
+    fn substr(&self, sub: &str) -> Self {
+        let Some(static_str) = self.0.as_static_str() else {
+            return Self::from_str_trunc(sub);
+        };
+
+        // SAFETY: `sub` is a substring of `self`, so is within the same allocation.
+        let offset = unsafe { sub.as_ptr().offset_from(self.as_ptr()) };
+        let start = usize::try_from(offset).expect("sub should start within self");
+
+        Self::from_static_trunc(&static_str[start..start + sub.len()])
+    }
+
+    fn slice_bounds(&self, start: Bound<usize>, end: Bound<usize>) -> Self {
+        let start = match start {
+            Bound::Included(start) => start,
+            Bound::Excluded(start) => start.checked_add(1).expect("range start overflowed"),
+            Bound::Unbounded => 0,
+        };
+
+        let end = match end {
+            Bound::Included(end) => end.checked_add(1).expect("range end overflowed"),
+            Bound::Excluded(end) => end,
+            Bound::Unbounded => self.as_str().len(),
+        };
+
+        self.substr(&self.as_str()[start..end])
+    }
+
+    /// Creates a [`FixedString`] of the bytes of `self` within `range`, in the same way as indexing a [`str`].
+    ///
+    /// If `self` was created from a `&'static str`, this does not copy, otherwise the result is stored inline if it
+    /// fits, or copied into a single allocation.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds, or either end is not on a char boundary.
+    #[must_use]
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Self {
+        self.slice_bounds(range.start_bound().cloned(), range.end_bound().cloned())
+    }
+
+    /// As [`Self::slice`], but with the range given in `LenT`, such as from [`Self::len`].
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds, or either end is not on a char boundary.
+    #[must_use]
+    pub fn slice_by_len(&self, range: impl RangeBounds<LenT>) -> Self {
+        let to_usize = |bound: Bound<&LenT>| match bound {
+            Bound::Included(val) => Bound::Included(val.to_usize()),
+            Bound::Excluded(val) => Bound::Excluded(val.to_usize()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        self.slice_bounds(to_usize(range.start_bound()), to_usize(range.end_bound()))
+    }
+
+    /// Creates a [`FixedString`] with leading and trailing whitespace removed, as in [`str::trim`].
+    ///
+    /// The [`str`] methods are still available through [`Deref`], so this is named differently to return a
+    /// [`FixedString`]. See [`Self::slice`] for when this copies.
+    ///
+    /// [`Deref`]: core::ops::Deref
+    #[must_use]
+    pub fn trimmed(&self) -> Self {
+        self.substr(self.as_str().trim())
+    }
+
+    /// Creates a [`FixedString`] with leading whitespace removed, as in [`str::trim_start`].
+    ///
+    /// See [`Self::slice`] for when this copies.
+    #[must_use]
+    pub fn trimmed_start(&self) -> Self {
+        self.substr(self.as_str().trim_start())
+    }
+
+    /// Creates a [`FixedString`] with trailing whitespace removed, as in [`str::trim_end`].
+    ///
+    /// See [`Self::slice`] for when this copies.
+    #[must_use]
+    pub fn trimmed_end(&self) -> Self {
+        self.substr(self.as_str().trim_end())
+    }
+
+    /// Creates a [`FixedString`] with `prefix` removed, as in [`str::strip_prefix`].
+    ///
+    /// See [`Self::slice`] for when this copies.
+    #[must_use]
+    pub fn without_prefix(&self, prefix: &str) -> Option<Self> {
+        self.as_str()
+            .strip_prefix(prefix)
+            .map(|sub| self.substr(sub))
+    }
+
+    /// Creates a [`FixedString`] with `suffix` removed, as in [`str::strip_suffix`].
+    ///
+    /// See [`Self::slice`] for when this copies.
+    #[must_use]
+    pub fn without_suffix(&self, suffix: &str) -> Option<Self> {
+        self.as_str()
+            .strip_suffix(suffix)
+            .map(|sub| self.substr(sub))
+    }
+
+    /// Collects an iterator of [`char`]s or strings into a [`FixedString`], stopping as soon as the collected string is
+    /// longer than `LenT::MAX` bytes.
+    ///
+    /// # Errors
+    /// If the collected string is longer than `LenT::MAX` bytes, the string collected so far is returned.
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, InvalidStrLength<LenT>>
+    where
+        I: IntoIterator,
+        String: Extend<I::Item>,
+    {
+        let max_len = LenT::MAX.to_usize();
+        Self::try_from(collect_string(iter, max_len.saturating_add(1)).into_boxed_str())
+    }
+
+    /// Collects an iterator of [`char`]s or strings into a [`FixedString`], **truncating** it to `LenT::MAX` bytes.
+    ///
+    /// The iterator will not be polled after more than `LenT::MAX` bytes have been collected.
+    /// See [`Self::from_string_trunc`] for truncation behaviour, with the original length reported as the number of
+    /// bytes collected.
+    #[must_use]
+    #[track_caller]
+    pub fn from_iter_trunc<I>(iter: I) -> Self
+    where
+        I: IntoIterator,
+        String: Extend<I::Item>,
+    {
+        let max_len = LenT::MAX.to_usize();
+        Self::from_string_trunc(collect_string(iter, max_len.saturating_add(1)))
+    }
+
+    /// Returns the length of the [`FixedString`].
+    #[must_use]
+    pub const fn len(&self) -> LenT {
+        self.0.len()
     }
 
     /// Returns if the length is equal to 0.
     #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.len() == LenT::ZERO
+    pub const fn is_empty(&self) -> bool {
+        self.0.as_str().is_empty()
+    }
+
+    /// Returns where the bytes of the [`FixedString`] are stored.
+    ///
+    /// Short strings are stored [`StorageKind::Inline`], and strings created from a `&'static str` with
+    /// [`Self::from_static_trunc`] are [`StorageKind::Static`] until they are mutated.
+    #[must_use]
+    pub const fn storage_kind(&self) -> StorageKind {
+        self.0.kind()
+    }
+
+    /// Returns the number of bytes the [`FixedString`] has allocated on the heap.
+    ///
+    /// See [`FixedArray::heap_bytes`].
+    #[must_use]
+    pub const fn heap_bytes(&self) -> usize {
+        self.0.heap_bytes()
+    }
+
+    /// Moves a [`StorageKind::Heap`] string which would fit inline out of its allocation, freeing it.
+    ///
+    /// Strings are stored inline when created if they fit, but conversions which keep an existing allocation, such as
+    /// [`Self::from_utf8`], may leave short strings on the heap.
+    pub fn compact(&mut self) {
+        if self.storage_kind() != StorageKind::Heap {
+            return;
+        }
+
+        if let Some(inline) = Self::new_inline(self) {
+            *self = inline;
+        }
+    }
+
+    /// Leaks the allocation of a [`StorageKind::Heap`] string, turning it into a [`StorageKind::Static`] string which
+    /// can be cloned without allocating.
+    ///
+    /// This is intended for strings which are created once and never freed, such as data loaded at startup.
+    /// Inline and static strings do not own an allocation, so are returned unchanged.
+    #[must_use]
+    pub fn leak(self) -> Self {
+        if self.storage_kind() != StorageKind::Heap {
+            return self;
+        }
+
+        let leaked: &'static [u8] = Box::leak(self.into_string().into_bytes().into_boxed_slice());
+
+        // SAFETY: `leaked` is valid for `'static`, and `u8` is `Copy` and `Sync`.
+        let array = unsafe { FixedArray::from_static(NonNull::from(leaked)) };
+
+        // SAFETY: The bytes were taken from a `FixedString`, so are valid UTF-8.
+        unsafe { Self::from_array_unchecked(array) }
     }
 
     /// Converts `&`[`FixedString`] to `&str`, this conversion can be performed by [`core::ops::Deref`].
     #[must_use]
-    pub fn as_str(&self) -> &str {
-        self
+    pub const fn as_str(&self) -> &str {
+        self.0.as_str()
     }
 
     /// Converts [`FixedString`] to [`String`], this operation should be cheap.
@@ -130,16 +576,111 @@ impl<LenT: ValidLength> FixedString<LenT> {
         self.into()
     }
 
+    /// Converts a [`FixedArray<u8>`] into a [`FixedString`] if it is valid UTF-8, reusing the allocation.
+    ///
+    /// # Errors
+    /// If the bytes are not valid UTF-8, the original array is returned along with the [`Utf8Error`](core::str::Utf8Error).
+    pub fn from_utf8(bytes: FixedArray<u8, LenT>) -> Result<Self, FromUtf8Error<LenT>> {
+        match core::str::from_utf8(&bytes) {
+            // SAFETY: The bytes have been checked to be valid UTF-8.
+            Ok(_) => Ok(unsafe { Self::from_utf8_unchecked(bytes) }),
+            Err(error) => Err(FromUtf8Error { bytes, error }),
+        }
+    }
+
+    /// Converts a [`FixedArray<u8>`] into a [`FixedString`], replacing invalid UTF-8 with
+    /// [`char::REPLACEMENT_CHARACTER`] as in [`String::from_utf8_lossy`].
+    ///
+    /// Valid UTF-8 reuses the allocation, otherwise the replaced string is copied into a new allocation.
+    ///
+    /// See [`Self::from_string_trunc`] for truncation behaviour, as replacement characters may be longer than the
+    /// bytes they replace.
+    #[must_use]
+    pub fn from_utf8_lossy(bytes: FixedArray<u8, LenT>) -> Self {
+        match Self::from_utf8(bytes) {
+            Ok(string) => string,
+            Err(err) => {
+                Self::from_string_trunc(String::from_utf8_lossy(err.as_bytes()).into_owned())
+            }
+        }
+    }
+
+    /// Converts a [`FixedArray<u8>`] into a [`FixedString`] without checking that it is valid UTF-8, reusing the
+    /// allocation.
+    ///
+    /// # Safety
+    /// - `bytes` must be valid UTF-8.
+    #[must_use]
+    pub unsafe fn from_utf8_unchecked(bytes: FixedArray<u8, LenT>) -> Self {
+        if bytes.storage_kind() == StorageKind::Inline {
+            // SAFETY: The caller has guaranteed that the bytes are valid UTF-8.
+            let string = unsafe { core::str::from_utf8_unchecked(&bytes) };
+            if let Some(inline) = Self::new_inline(string) {
+                return inline;
+            }
+        }
+
+        // SAFETY: The caller has guaranteed that the bytes are valid UTF-8.
+        unsafe { Self::from_array_unchecked(bytes) }
+    }
+
+    /// Converts [`FixedString`] to [`FixedArray<u8>`], reusing the allocation if the string is not inline.
+    #[must_use]
+    pub fn into_bytes(self) -> FixedArray<u8, LenT> {
+        match self.0.into_unpacked() {
+            UnpackedRepr::Inline(inline) => {
+                let len = LenT::from_usize(inline.as_str().len()).unwrap_or_else(|| {
+                    unreachable!("inline strings are checked against LenT::MAX")
+                });
+
+                FixedArray::from_parts(len, |push| push(inline.as_str().as_bytes()))
+            }
+            UnpackedRepr::Array(array) => array,
+        }
+    }
+
+    /// Mutates the [`FixedString`] through a [`String`], reusing the existing allocation, then converts it back,
+    /// storing it inline if it now fits.
+    ///
+    /// `self` is cloned first, so it is left unchanged if `f` panics.
+    ///
+    /// # Errors
+    /// If the modified string is longer than `LenT::MAX` bytes, `self` is left unchanged and the modified string is
+    /// returned.
+    pub fn modify(&mut self, f: impl FnOnce(&mut String)) -> Result<(), InvalidStrLength<LenT>> {
+        let original = self.clone();
+        let mut string = core::mem::take(self).into_string();
+        let restore = RestoreOnDrop::new(self, original);
+
+        f(&mut string);
+        restore.finish(Self::try_from(string)?);
+        Ok(())
+    }
+
+    /// Mutates the [`FixedString`] through a [`String`], reusing the existing allocation, then converts it back,
+    /// storing it inline if it now fits.
+    ///
+    /// If `f` panics, `self` is left empty.
+    ///
+    /// See [`Self::from_string_trunc`] for truncation behaviour.
+    #[track_caller]
+    pub fn modify_trunc(&mut self, f: impl FnOnce(&mut String)) {
+        let mut string = core::mem::take(self).into_string();
+        f(&mut string);
+
+        *self = Self::from_string_trunc(string);
+    }
+
     #[cfg(test)]
     #[must_use]
     pub(crate) fn is_inline(&self) -> bool {
-        matches!(self, Self(FixedStringRepr::Inline(_)))
+        self.storage_kind() == StorageKind::Inline
     }
 
     #[cfg(test)]
     #[must_use]
     pub(crate) fn is_static(&self) -> bool {
-        matches!(self, Self(FixedStringRepr::Static(_)))
+        self.storage_kind() == StorageKind::Static
     }
 }
 
@@ -147,12 +688,7 @@ impl<LenT: ValidLength> core::ops::Deref for FixedString<LenT> {
     type Target = str;
 
     fn deref(&self) -> &Self::Target {
-        match &self.0 {
-            // SAFETY: Self holds the type invariant that the array is UTF-8.
-            FixedStringRepr::Heap(a) => unsafe { core::str::from_utf8_unchecked(a) },
-            FixedStringRepr::Static(a) => a.as_str(),
-            FixedStringRepr::Inline(a) => a.as_str(),
-        }
+        self.0.as_str()
     }
 }
 
@@ -164,19 +700,20 @@ impl<LenT: ValidLength> Default for FixedString<LenT> {
 
 impl<LenT: ValidLength> Clone for FixedString<LenT> {
     fn clone(&self) -> Self {
-        match &self.0 {
-            FixedStringRepr::Heap(a) => Self(FixedStringRepr::Heap(a.clone())),
-            FixedStringRepr::Inline(a) => Self(FixedStringRepr::Inline(*a)),
-            FixedStringRepr::Static(a) => Self(FixedStringRepr::Static(*a)),
-        }
+        Self(self.0.clone())
     }
 
+    #[allow(clippy::assigning_clones)]
     fn clone_from(&mut self, source: &Self) {
-        match (&mut self.0, &source.0) {
-            (FixedStringRepr::Heap(new), FixedStringRepr::Heap(src)) => new.clone_from(src),
-            #[allow(clippy::assigning_clones)]
-            _ => *self = source.clone(),
+        // SAFETY: The bytes are overwritten with the UTF-8 bytes of `source`.
+        if let Some(new) = unsafe { self.0.heap_bytes_mut() } {
+            if source.0.kind() == StorageKind::Heap && new.len() == source.len().to_usize() {
+                new.copy_from_slice(source.as_bytes());
+                return;
+            }
         }
+
+        *self = source.clone();
     }
 }
 
@@ -249,13 +786,22 @@ impl<LenT: ValidLength> core::fmt::Display for FixedString<LenT> {
 }
 
 impl<LenT: ValidLength> core::fmt::Debug for FixedString<LenT> {
+    /// Formats the string as a `str`, or with `{:#?}`, alongside the length and [`StorageKind`].
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{:?}", self.as_str())
+        if f.alternate() {
+            f.debug_struct("FixedString")
+                .field("value", &self.as_str())
+                .field("len", &self.len())
+                .field("storage", &self.storage_kind())
+                .finish()
+        } else {
+            write!(f, "{:?}", self.as_str())
+        }
     }
 }
 
 impl<LenT: ValidLength> FromStr for FixedString<LenT> {
-    type Err = InvalidStrLength;
+    type Err = InvalidStrLength<LenT>;
 
     fn from_str(val: &str) -> Result<Self, Self::Err> {
         if let Some(inline) = Self::new_inline(val) {
@@ -267,7 +813,7 @@ impl<LenT: ValidLength> FromStr for FixedString<LenT> {
 }
 
 impl<LenT: ValidLength> TryFrom<Box<str>> for FixedString<LenT> {
-    type Error = InvalidStrLength;
+    type Error = InvalidStrLength<LenT>;
 
     fn try_from(value: Box<str>) -> Result<Self, Self::Error> {
         if let Some(inline) = Self::new_inline(&value) {
@@ -275,7 +821,7 @@ impl<LenT: ValidLength> TryFrom<Box<str>> for FixedString<LenT> {
         }
 
         match value.into_boxed_bytes().try_into() {
-            Ok(val) => Ok(Self(FixedStringRepr::Heap(val))),
+            Ok(val) => Ok(Self(FixedStringRepr::from_array(val))),
             Err(err) => Err(err
                 .try_into()
                 .expect("Box<str> -> Box<[u8]> should stay valid UTF8")),
@@ -284,7 +830,7 @@ impl<LenT: ValidLength> TryFrom<Box<str>> for FixedString<LenT> {
 }
 
 impl<LenT: ValidLength> TryFrom<String> for FixedString<LenT> {
-    type Error = InvalidStrLength;
+    type Error = InvalidStrLength<LenT>;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         if let Some(inline) = Self::new_inline(&value) {
@@ -299,8 +845,9 @@ impl<LenT: ValidLength> From<char> for FixedString<LenT> {
     fn from(value: char) -> Self {
         use alloc::vec;
 
-        if let Some(value) = InlineString::from_char(value) {
-            return Self(FixedStringRepr::Inline(value));
+        let fits = LenT::from_usize(value.len_utf8()).is_some();
+        if let Some(value) = InlineString::from_char(value).filter(|_| fits) {
+            return Self(FixedStringRepr::from_inline(value));
         }
 
         let mut bytes = vec![0; value.len_utf8()].into_boxed_slice();
@@ -309,9 +856,9 @@ impl<LenT: ValidLength> From<char> for FixedString<LenT> {
 
         let bytes = bytes
             .try_into()
-            .expect("len_utf8 is at most 4, so it will fit in u8");
+            .expect("len_utf8 is at most 4, which should fit into LenT");
 
-        Self(FixedStringRepr::Heap(bytes))
+        Self(FixedStringRepr::from_array(bytes))
     }
 }
 
@@ -323,11 +870,10 @@ impl<LenT: ValidLength> From<FixedString<LenT>> for String {
 
 impl<LenT: ValidLength> From<FixedString<LenT>> for Box<str> {
     fn from(value: FixedString<LenT>) -> Self {
-        match value.0 {
-            FixedStringRepr::Inline(a) => a.as_str().into(),
-            FixedStringRepr::Static(a) => a.as_str().into(),
+        match value.0.into_unpacked() {
+            UnpackedRepr::Inline(a) => a.as_str().into(),
             // SAFETY: Self holds the type invariant that the array is UTF-8.
-            FixedStringRepr::Heap(a) => unsafe { alloc::str::from_boxed_utf8_unchecked(a.into()) },
+            UnpackedRepr::Array(a) => unsafe { alloc::str::from_boxed_utf8_unchecked(a.into()) },
         }
     }
 }
@@ -340,9 +886,15 @@ impl<'a, LenT: ValidLength> From<&'a FixedString<LenT>> for Cow<'a, str> {
 
 impl<LenT: ValidLength> From<FixedString<LenT>> for Cow<'_, str> {
     fn from(value: FixedString<LenT>) -> Self {
-        match value.0 {
-            FixedStringRepr::Static(static_str) => Cow::Borrowed(static_str.as_str()),
-            _ => Cow::Owned(value.into()),
+        match value.0.into_unpacked() {
+            UnpackedRepr::Inline(a) => Cow::Owned(a.as_str().into()),
+            // SAFETY: Self holds the type invariant that the array is UTF-8.
+            UnpackedRepr::Array(a) => match Cow::<[u8]>::from(a) {
+                Cow::Borrowed(bytes) => {
+                    Cow::Borrowed(unsafe { core::str::from_utf8_unchecked(bytes) })
+                }
+                Cow::Owned(bytes) => Cow::Owned(unsafe { String::from_utf8_unchecked(bytes) }),
+            },
         }
     }
 }
@@ -426,6 +978,7 @@ impl<LenT: ValidLength> serde::Serialize for FixedString<LenT> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{inline::get_heap_threshold, Bounded, U24};
 
     fn check_u8_roundtrip_generic(to_fixed: fn(String) -> FixedString<u8>) {
         for i in 0..=u8::MAX {
@@ -436,27 +989,38 @@ mod test {
             assert_eq!(fixed.len(), i);
 
             if !fixed.is_static() {
-                assert_eq!(fixed.is_inline(), fixed.len() <= 9);
+                assert_eq!(fixed.is_inline(), fixed.len() <= 10);
             }
         }
     }
 
     // primarily intended to ensure no hangs occur
-    #[cfg(any(target_pointer_width = "64", target_pointer_width = "32"))]
-    fn check_u32_partial_roundtrip_generic(to_fixed: fn(String) -> FixedString<u32>) {
-        for i in 0..=400u32 {
-            let original = "a".repeat(i as usize);
+    fn check_partial_roundtrip_generic<LenT: ValidLength>(
+        to_fixed: fn(String) -> FixedString<LenT>,
+    ) {
+        for i in 0..=400 {
+            let original = "a".repeat(i);
             let fixed = to_fixed(original);
 
             assert!(fixed.bytes().all(|c| c == b'a'));
-            assert_eq!(fixed.len(), i);
+            assert_eq!(fixed.len().to_usize(), i);
 
             if !fixed.is_static() {
-                assert_eq!(fixed.is_inline(), fixed.len() <= 12);
+                assert_eq!(fixed.is_inline(), i <= get_heap_threshold::<LenT>());
             }
         }
     }
 
+    fn check_partial_roundtrip_all<LenT: ValidLength>() {
+        check_partial_roundtrip_generic::<LenT>(|original| {
+            FixedString::try_from(original).unwrap()
+        });
+        check_partial_roundtrip_generic::<LenT>(|original| {
+            let static_str = Box::leak(original.into_boxed_str());
+            FixedString::from_static_trunc(static_str)
+        });
+    }
+
     fn check_default_generic<LenT: ValidLength>() {
         let fixed = FixedString::<LenT>::default();
 
@@ -474,6 +1038,61 @@ mod test {
 
         assert_eq!(str, string.as_str());
         assert_ne!(STR, str);
+
+        let (string, dropped) = FixedString::<u8>::from_static_trunc_report(STR);
+        assert_eq!((string.len(), dropped), (254, 4));
+
+        let (string, dropped) = FixedString::<u8>::from_string_trunc_report("🦀".repeat(100));
+        assert_eq!((string.len(), dropped), (252, 148));
+
+        let (string, dropped) = FixedString::<u8>::from_str_trunc_report("hello");
+        assert_eq!((string.as_str(), dropped), ("hello", 0));
+    }
+
+    #[test]
+    fn check_storage_kind() {
+        let inline = FixedString::<u8>::from_str_trunc("hello");
+        assert_eq!(inline.storage_kind(), StorageKind::Inline);
+        assert_eq!(inline.heap_bytes(), 0);
+
+        let heap = FixedString::<u8>::from_str_trunc(&"a".repeat(20));
+        assert_eq!(heap.storage_kind(), StorageKind::Heap);
+        assert_eq!(heap.heap_bytes(), 20);
+
+        let static_string = FixedString::<u8>::from_static_trunc("a static string");
+        assert_eq!(static_string.storage_kind(), StorageKind::Static);
+        assert_eq!(static_string.heap_bytes(), 0);
+
+        assert_eq!(format!("{inline:?}"), "\"hello\"");
+        assert_eq!(
+            format!("{inline:#?}"),
+            "FixedString {\n    value: \"hello\",\n    len: 5,\n    storage: Inline,\n}"
+        );
+    }
+
+    #[test]
+    fn check_compact_and_leak() {
+        let boxed = FixedArray::<u8, u8>::from_boxed_heap(Box::from(*b"hello"));
+        // SAFETY: The bytes are ASCII.
+        let mut heap = unsafe { FixedString::from_array_unchecked(boxed) };
+        assert_eq!(heap.storage_kind(), StorageKind::Heap);
+
+        heap.compact();
+        assert_eq!(heap.storage_kind(), StorageKind::Inline);
+        assert_eq!(heap, "hello");
+
+        let mut long = FixedString::<u8>::from_str_trunc(&"a".repeat(20));
+        long.compact();
+        assert_eq!(long.storage_kind(), StorageKind::Heap);
+
+        let leaked = long.leak();
+        assert_eq!(leaked.storage_kind(), StorageKind::Static);
+        assert_eq!(leaked.heap_bytes(), 0);
+        assert_eq!(leaked, "a".repeat(20));
+        assert_eq!(leaked.clone().as_ptr(), leaked.as_ptr());
+
+        let inline = FixedString::<u8>::from_str_trunc("hi").leak();
+        assert_eq!(inline.storage_kind(), StorageKind::Inline);
     }
 
     #[test]
@@ -511,7 +1130,7 @@ mod test {
     #[test]
     #[cfg(any(target_pointer_width = "64", target_pointer_width = "32"))]
     fn check_u32_partial_roundtrip() {
-        check_u32_partial_roundtrip_generic(|original| {
+        check_partial_roundtrip_generic::<u32>(|original| {
             FixedString::<u32>::try_from(original).unwrap()
         });
     }
@@ -519,12 +1138,29 @@ mod test {
     #[test]
     #[cfg(any(target_pointer_width = "64", target_pointer_width = "32"))]
     fn check_u32_partial_roundtrip_static() {
-        check_u32_partial_roundtrip_generic(|original| {
+        check_partial_roundtrip_generic::<u32>(|original| {
             let static_str = Box::leak(original.into_boxed_str());
             FixedString::from_static_trunc(static_str)
         });
     }
 
+    #[test]
+    #[cfg(any(target_pointer_width = "64", target_pointer_width = "32"))]
+    fn check_u24_partial_roundtrip() {
+        check_partial_roundtrip_all::<U24>();
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn check_u64_partial_roundtrip() {
+        check_partial_roundtrip_all::<u64>();
+    }
+
+    #[test]
+    fn check_usize_partial_roundtrip() {
+        check_partial_roundtrip_all::<usize>();
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn check_u8_roundtrip_serde() {
@@ -563,6 +1199,246 @@ mod test {
         check_default_generic::<u32>();
     }
 
+    #[test]
+    fn check_from_iter() {
+        let string = FixedString::<u8>::try_from_iter("hello".chars().rev()).unwrap();
+        assert_eq!(string, "olleh");
+
+        let string = FixedString::<u8>::try_from_iter(["hello", " ", "world"]).unwrap();
+        assert_eq!(string, "hello world");
+
+        let parts = [String::from("a"), String::from("b")];
+        assert_eq!(FixedString::<u8>::try_from_iter(parts).unwrap(), "ab");
+
+        // An infinite iterator must stop as soon as LenT::MAX is exceeded.
+        let err = FixedString::<u8>::try_from_iter(core::iter::repeat('a')).unwrap_err();
+        assert_eq!(err.get_inner().len(), 256);
+
+        let string = FixedString::<u8>::from_iter_trunc(core::iter::repeat("🦀"));
+        assert_eq!(string.len(), 252);
+        assert!(string.chars().all(|c| c == '🦀'));
+    }
+
+    #[test]
+    fn check_default_other_lengths() {
+        #[cfg(any(target_pointer_width = "64", target_pointer_width = "32"))]
+        check_default_generic::<U24>();
+        #[cfg(target_pointer_width = "64")]
+        check_default_generic::<u64>();
+        check_default_generic::<usize>();
+    }
+
+    #[test]
+    fn check_modify() {
+        let mut string = FixedString::<u8>::from_static_trunc("hello");
+        string.modify(|s| s.push_str(" world")).unwrap();
+        assert!(!string.is_static());
+        assert_eq!(string, "hello world");
+
+        let ptr = string.as_ptr();
+        string.modify(|s| s.make_ascii_uppercase()).unwrap();
+        assert_eq!(string.as_ptr(), ptr);
+        assert_eq!(string, "HELLO WORLD");
+
+        string.modify(|s| s.truncate(2)).unwrap();
+        assert!(string.is_inline());
+        assert_eq!(string, "HE");
+
+        let err = string.modify(|s| s.push_str(&"a".repeat(300))).unwrap_err();
+        assert_eq!(err.get_inner().len(), 302);
+        assert_eq!(string, "HE");
+
+        #[cfg(feature = "std")]
+        {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                string.modify(|s| {
+                    s.clear();
+                    panic!("modify panicked");
+                })
+            }));
+
+            assert!(result.is_err());
+            assert_eq!(string, "HE");
+        }
+
+        string.modify_trunc(|s| s.push_str(&"a".repeat(300)));
+        assert_eq!(string.len(), u8::MAX);
+
+        let ptr = string.as_ptr();
+        string.modify_trunc(|s| s.make_ascii_uppercase());
+        assert_eq!(string.as_ptr(), ptr);
+        assert_eq!(string, format!("HE{}", "A".repeat(253)));
+    }
+
+    #[test]
+    fn check_concat() {
+        let inline = FixedString::<u8>::concat(&["ab", ":", "cd"]).unwrap();
+        assert!(inline.is_inline());
+        assert_eq!(inline, "ab:cd");
+
+        let heap = FixedString::<u8>::concat(&["prefix".to_owned(), ":".repeat(20)]).unwrap();
+        assert!(!heap.is_inline());
+        assert_eq!(heap.len(), 26);
+
+        let joined = FixedString::<u8>::join(["a", "b", "c"], ", ").unwrap();
+        assert_eq!(joined, "a, b, c");
+
+        let joined = FixedString::<u8>::join(["🦀".repeat(10), "🦀".repeat(10)], "-").unwrap();
+        assert_eq!(joined.len(), 81);
+        assert_eq!(FixedString::<u8>::join([""; 0], "-").unwrap(), "");
+
+        let repeated = FixedString::<u8>::from_static_trunc("ab")
+            .repeated(3)
+            .unwrap();
+        assert!(repeated.is_inline());
+        assert_eq!(repeated, "ababab");
+        assert_eq!(FixedString::<u8>::new().repeated(usize::MAX).unwrap(), "");
+
+        let err = FixedString::<u8>::from_static_trunc("ab")
+            .repeated(128)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            LengthError::TooLong {
+                max: u8::MAX,
+                actual: 256
+            }
+        );
+
+        let err = FixedString::<u8>::from_static_trunc("ab")
+            .repeated(usize::MAX)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            LengthError::TooLong {
+                actual: usize::MAX,
+                ..
+            }
+        ));
+
+        let err = FixedString::<Bounded<u8, 4>>::concat(&["ab", "cde"]).unwrap_err();
+        assert!(matches!(err, LengthError::TooLong { actual: 5, .. }));
+        let err = FixedString::<u8>::join(["a"; 200], "-").unwrap_err();
+        assert!(matches!(err, LengthError::TooLong { actual: 399, .. }));
+    }
+
+    #[test]
+    fn check_slice() {
+        static COMMAND: &str = "!ping   some arguments  ";
+
+        let command = FixedString::<u8>::from_static_trunc(COMMAND);
+        let args = command.without_prefix("!ping").unwrap().trimmed();
+        assert!(args.is_static());
+        assert_eq!(args, "some arguments");
+        assert_eq!(args.as_ptr(), COMMAND[8..].as_ptr());
+
+        let name = command.slice(1..5);
+        assert!(name.is_static());
+        assert_eq!(name, "ping");
+        assert_eq!(command.slice_by_len(1..5), "ping");
+        assert_eq!(command.slice(..=4), "!ping");
+        assert_eq!(command.slice_by_len(command.len() - 2..), "  ");
+        assert!(command.without_suffix("!").is_none());
+
+        let heap = FixedString::<u8>::from_str_trunc(&format!("  {}  ", "a".repeat(20)));
+        let trimmed = heap.trimmed_start();
+        assert!(!trimmed.is_inline() && !trimmed.is_static());
+        assert_eq!(trimmed.len(), 22);
+
+        let inline = heap.trimmed_end().slice(..4);
+        assert!(inline.is_inline());
+        assert_eq!(inline, "  aa");
+    }
+
+    #[test]
+    #[should_panic = "char boundary"]
+    fn check_slice_char_boundary() {
+        let _ = FixedString::<u8>::from_static_trunc("🦀").slice(1..);
+    }
+
+    #[test]
+    fn check_from_utf8() {
+        let bytes = FixedArray::<u8, u8>::from_vec_trunc("a".repeat(20).into_bytes());
+        let ptr = bytes.as_ptr();
+
+        let string = FixedString::from_utf8(bytes).unwrap();
+        assert_eq!(string.as_ptr(), ptr);
+        assert_eq!(string, "a".repeat(20));
+
+        let bytes = string.into_bytes();
+        assert_eq!(bytes.as_ptr(), ptr);
+
+        let inline = FixedString::<u8>::from_utf8(FixedArray::from(*b"hi")).unwrap();
+        assert!(inline.is_inline());
+        assert_eq!(inline, "hi");
+        assert_eq!(inline.into_bytes().as_slice(), b"hi");
+
+        let threshold = get_heap_threshold::<u8>();
+        let full = FixedString::<u8>::from_str_trunc(&"b".repeat(threshold));
+        assert!(full.is_inline());
+        assert_eq!(full.into_bytes().len().to_usize(), threshold);
+
+        let err = FixedString::from_utf8(FixedArray::<u8, u8>::from(*b"a\xFFb")).unwrap_err();
+        assert_eq!(err.utf8_error().valid_up_to(), 1);
+        assert_eq!(err.get_inner().as_slice(), b"a\xFFb");
+
+        let lossy = FixedString::from_utf8_lossy(FixedArray::<u8, u8>::from(*b"a\xFFb"));
+        assert_eq!(lossy, "a\u{FFFD}b");
+
+        let lossy =
+            FixedString::from_utf8_lossy(FixedArray::<u8, Bounded<u8, 4>>::from(*b"\xFF\xFF"));
+        assert_eq!(lossy, "\u{FFFD}");
+    }
+
+    #[test]
+    fn check_const() {
+        const EMPTY: FixedString = FixedString::new();
+        static NAME: FixedString<u8> = FixedString::const_from_static_trunc("small-fixed-array");
+        static TRUNCATED: (FixedString<Bounded<u8, 3>>, usize) =
+            FixedString::const_from_static_trunc_report("naïve");
+
+        assert!(EMPTY.is_empty());
+        assert_eq!(EMPTY.as_str(), "");
+
+        assert!(NAME.is_static());
+        assert_eq!(NAME.as_str(), "small-fixed-array");
+        assert_eq!(NAME.len(), 17);
+
+        // Truncated down to the nearest char boundary.
+        assert_eq!(TRUNCATED.0.as_str(), "na");
+        assert_eq!(TRUNCATED.1, 4);
+    }
+
+    #[test]
+    fn check_bounded() {
+        type Small = Bounded<u8, 4>;
+        type Large = Bounded<u16, 300>;
+
+        assert_eq!(
+            core::mem::size_of::<FixedString<Small>>(),
+            core::mem::size_of::<FixedString<u8>>()
+        );
+
+        // Short enough to be inline, but still above MAX.
+        assert!(FixedString::<Small>::from_str("hello").is_err());
+        assert_eq!(
+            FixedString::<Small>::from_str_trunc("hello").as_str(),
+            "hell"
+        );
+        assert_eq!(
+            FixedString::<Small>::from_str("hey").unwrap().len().get(),
+            3
+        );
+        assert_eq!(FixedString::<Small>::from('🦀').as_str(), "🦀");
+
+        let long = "a".repeat(301);
+        assert!(FixedString::<Large>::try_from(long.clone()).is_err());
+        assert_eq!(
+            FixedString::<Large>::from_string_trunc(long).len().get(),
+            300
+        );
+    }
+
     #[test]
     fn check_sizes() {
         type DoubleOpt<T> = Option<Option<T>>;
@@ -570,14 +1446,117 @@ mod test {
         assert_eq!(core::mem::size_of::<Option<InlineString<[u8; 11]>>>(), 12);
         assert_eq!(core::mem::align_of::<Option<InlineString<[u8; 11]>>>(), 1);
         assert_eq!(core::mem::size_of::<Option<FixedArray<u8, u32>>>(), 12);
-        // https://github.com/rust-lang/rust/issues/119507
-        assert_eq!(core::mem::size_of::<DoubleOpt<FixedArray<u8, u32>>>(), 13);
+        assert_eq!(core::mem::size_of::<DoubleOpt<FixedArray<u8, u32>>>(), 12);
         assert_eq!(core::mem::align_of::<Option<FixedArray<u8, u32>>>(), 1);
-        // This sucks!! I want to fix this, soon.... this should so niche somehow.
-        assert_eq!(core::mem::size_of::<FixedStringRepr<u32>>(), 13);
+        assert_eq!(core::mem::size_of::<FixedStringRepr<u32>>(), 12);
         assert_eq!(core::mem::align_of::<FixedStringRepr<u32>>(), 1);
     }
 
+    fn check_sizes_generic<LenT: ValidLength>(expected: usize) {
+        type TripleOpt<T> = Option<Option<Option<T>>>;
+
+        assert_eq!(core::mem::size_of::<FixedString<LenT>>(), expected);
+        assert_eq!(core::mem::size_of::<Option<FixedString<LenT>>>(), expected);
+        assert_eq!(
+            core::mem::size_of::<TripleOpt<FixedString<LenT>>>(),
+            expected
+        );
+        assert_eq!(core::mem::align_of::<Option<FixedString<LenT>>>(), 1);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn check_sizes_all_lengths() {
+        check_sizes_generic::<u8>(10);
+        check_sizes_generic::<u16>(11);
+        check_sizes_generic::<U24>(11);
+        check_sizes_generic::<u32>(12);
+        check_sizes_generic::<u64>(16);
+        check_sizes_generic::<usize>(16);
+        check_sizes_generic::<Bounded<u8, 100>>(10);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    #[cfg(target_pointer_width = "64")]
+    fn check_tagged_len_high_bits() {
+        let original = "a".repeat((1 << 24) + 5);
+
+        let fixed = FixedString::<u32>::try_from(original.clone()).unwrap();
+        assert_eq!(fixed.len().to_usize(), original.len());
+        assert_eq!(fixed, original);
+
+        let fixed = FixedString::<u32>::from_static_trunc(Box::leak(original.into_boxed_str()));
+        assert!(fixed.is_static());
+        assert_eq!(fixed.len(), (1 << 24) + 5);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn check_static_boxed_at_tag_limit() {
+        let original: &'static str = Box::leak("a".repeat((1 << 20) + 1).into_boxed_str());
+
+        let fixed = FixedString::<U24>::from_static_trunc(original);
+        assert!(fixed.is_static());
+        assert_eq!(fixed.as_str().as_ptr(), original.as_ptr());
+        assert_eq!(fixed.len().to_usize(), original.len());
+        assert_eq!(fixed.heap_bytes(), core::mem::size_of::<NonNull<[u8]>>());
+
+        let fits = FixedString::<U24>::from_static_trunc(&original[..(1 << 20) - 1]);
+        assert!(fits.is_static());
+        assert_eq!(fits.heap_bytes(), 0);
+    }
+
+    #[test]
+    fn check_small_len_repr() {
+        let original = "a".repeat(200);
+
+        let heap = FixedString::<u8>::from_str_trunc(&original);
+        assert_eq!(
+            heap.heap_bytes(),
+            200 + core::mem::size_of::<NonNull<[u8]>>()
+        );
+        assert_eq!(heap.clone(), original);
+        assert_eq!(Some(heap), Some(FixedString::from_str_trunc(&original)));
+
+        let static_str = FixedString::<u8>::from_static_trunc(Box::leak(original.into_boxed_str()));
+        assert!(static_str.is_static());
+        assert_eq!(static_str.len(), 200);
+
+        let short = FixedString::<u16>::from_str_trunc(&"a".repeat(100));
+        assert_eq!(short.heap_bytes(), 100);
+        assert_eq!(short.len(), 100);
+    }
+
+    #[test]
+    #[should_panic = "static array is too long to create without allocating"]
+    fn check_const_static_at_tag_limit() {
+        let original: &'static str = Box::leak("a".repeat((1 << 20) + 1).into_boxed_str());
+        let _ = FixedString::<U24>::const_from_static_trunc(original);
+    }
+
+    #[test]
+    fn check_boxed_repr() {
+        let original = "a".repeat(64);
+
+        let heap = FixedArray::<u8, u8>::from_boxed_heap(original.clone().into_bytes().into());
+        let heap = FixedString(FixedStringRepr::from_array(heap));
+        assert_eq!(heap.len(), 64);
+        assert_eq!(heap, original);
+        assert!(!heap.is_inline() && !heap.is_static());
+
+        let cloned = heap.clone();
+        assert_eq!(String::from(heap), original);
+        assert_eq!(cloned, original);
+
+        let static_str = Box::leak(original.clone().into_boxed_str());
+        let static_str = FixedArray::<u8, u8>::from_boxed_static(static_str.as_bytes());
+        let static_str = FixedString::<u8>(FixedStringRepr::from_array(static_str));
+        assert!(static_str.is_static());
+        assert_eq!(static_str.clone(), original);
+        assert_eq!(Cow::from(static_str), Cow::Borrowed(original.as_str()));
+    }
+
     #[test]
     fn from_char_u8() {
         let s: FixedString<u8> = 'a'.into();
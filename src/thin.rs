@@ -0,0 +1,577 @@
+use alloc::{
+    alloc::{alloc, dealloc, handle_alloc_error, Layout},
+    boxed::Box,
+    string::String,
+    vec::Vec,
+};
+use core::{borrow::Borrow, fmt::Debug, hash::Hash, marker::PhantomData, ptr::NonNull};
+
+use crate::{
+    error::{InvalidLength, InvalidStrLength},
+    length::{SmallLen, ValidLength},
+    FixedArray, FixedString,
+};
+
+/// The header pointed to by every empty [`ThinFixedArray`], which is never freed.
+///
+/// This is a [`u64`] to be aligned for, and zero when read as, any [`ValidLength`].
+static EMPTY_HEADER: u64 = 0;
+
+/// A [`FixedArray`] which stores the length in the allocation, keeping only a single pointer inline.
+///
+/// This can be thought of as `Box<[T]>`, except the length is denoted as `LenT`, by default [`u32`],
+/// and has to be read from the allocation. `Option<ThinFixedArray>` is the same size as a pointer.
+#[repr(transparent)]
+pub struct ThinFixedArray<T, LenT: ValidLength = SmallLen> {
+    header: NonNull<LenT>,
+    marker: PhantomData<T>,
+}
+
+impl<T, LenT: ValidLength> ThinFixedArray<T, LenT> {
+    /// Creates a new, empty [`ThinFixedArray`] that cannot be pushed to.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            header: NonNull::from(&EMPTY_HEADER).cast(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the layout of the allocation, and the offset of the elements.
+    fn layout(len: usize) -> (Layout, usize) {
+        let array = Layout::array::<T>(len).expect("array layout should be valid");
+        let (layout, offset) = Layout::new::<LenT>()
+            .extend(array)
+            .expect("array with header should fit into isize::MAX bytes");
+
+        (layout.pad_to_align(), offset)
+    }
+
+    /// Moves the elements of `vec` into a new allocation, leaving the original allocation to be freed.
+    ///
+    /// # Safety
+    /// - `len` must be equal to `vec.len()`
+    unsafe fn from_vec(mut vec: Vec<T>, len: LenT) -> Self {
+        debug_assert_eq!(len.to_usize(), vec.len());
+        if vec.is_empty() {
+            return Self::empty();
+        }
+
+        let (layout, offset) = Self::layout(vec.len());
+
+        // SAFETY: The layout always contains the header, so is never zero sized.
+        let Some(header) = NonNull::new(unsafe { alloc(layout) }) else {
+            handle_alloc_error(layout)
+        };
+
+        // SAFETY: The allocation fits the header followed by `vec.len()` elements at `offset`,
+        // and `vec` forgets the moved elements.
+        unsafe {
+            header.as_ptr().cast::<LenT>().write(len);
+
+            let elements = header.as_ptr().add(offset).cast::<T>();
+            core::ptr::copy_nonoverlapping(vec.as_ptr(), elements, vec.len());
+            vec.set_len(0);
+        }
+
+        Self {
+            header: header.cast(),
+            marker: PhantomData,
+        }
+    }
+
+    fn is_allocated(&self) -> bool {
+        self.header != NonNull::from(&EMPTY_HEADER).cast()
+    }
+
+    fn slice_ptr(&self) -> NonNull<[T]> {
+        let len = self.len().to_usize();
+        if len == 0 {
+            return NonNull::slice_from_raw_parts(NonNull::dangling(), 0);
+        }
+
+        let (_, offset) = Self::layout(len);
+
+        // SAFETY: Non-empty arrays are always allocated with the elements at `offset`.
+        let elements = unsafe { self.header.as_ptr().cast::<u8>().add(offset) };
+        NonNull::slice_from_raw_parts(NonNull::new(elements).unwrap().cast(), len)
+    }
+
+    /// Returns the length of the [`ThinFixedArray`].
+    ///
+    /// This reads the length from the allocation.
+    #[must_use]
+    pub fn len(&self) -> LenT {
+        // SAFETY: The header is always initialised, either by `Self::from_vec` or `EMPTY_HEADER`.
+        unsafe { self.header.as_ptr().read() }
+    }
+
+    /// Returns if the length is equal to 0.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        !self.is_allocated()
+    }
+
+    /// Converts `&`[`ThinFixedArray<T>`] to `&[T]`, this conversion can be performed by [`core::ops::Deref`].
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    /// Converts `&mut `[`ThinFixedArray<T>`] to `&mut [T]`, this conversion can be performed by [`core::ops::DerefMut`].
+    #[must_use]
+    pub fn as_slice_mut(&mut self) -> &mut [T] {
+        self
+    }
+
+    /// Converts [`ThinFixedArray<T>`] to [`Vec<T>`], this moves the elements into a new allocation.
+    #[must_use]
+    pub fn into_vec(self) -> Vec<T> {
+        self.into()
+    }
+}
+
+unsafe impl<T: Send, LenT: ValidLength> Send for ThinFixedArray<T, LenT> {}
+unsafe impl<T: Sync, LenT: ValidLength> Sync for ThinFixedArray<T, LenT> {}
+
+impl<T, LenT: ValidLength> core::ops::Deref for ThinFixedArray<T, LenT> {
+    type Target = [T];
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The slice pointer is either dangling and empty, or points to the initialised elements.
+        unsafe { self.slice_ptr().as_ref() }
+    }
+}
+
+impl<T, LenT: ValidLength> core::ops::DerefMut for ThinFixedArray<T, LenT> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The slice pointer is either dangling and empty, or points to the uniquely owned elements.
+        unsafe { self.slice_ptr().as_mut() }
+    }
+}
+
+impl<T, LenT: ValidLength> Drop for ThinFixedArray<T, LenT> {
+    fn drop(&mut self) {
+        if !self.is_allocated() {
+            return;
+        }
+
+        let (layout, _) = Self::layout(self.len().to_usize());
+
+        // SAFETY: The elements and allocation are never used again, as we are in the drop impl.
+        unsafe {
+            core::ptr::drop_in_place(self.slice_ptr().as_ptr());
+            dealloc(self.header.as_ptr().cast(), layout);
+        }
+    }
+}
+
+impl<T, LenT: ValidLength> Default for ThinFixedArray<T, LenT> {
+    /// Creates a new, empty [`ThinFixedArray`] that cannot be pushed to.
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T: Clone, LenT: ValidLength> Clone for ThinFixedArray<T, LenT> {
+    fn clone(&self) -> Self {
+        // SAFETY: The length is taken from the same array.
+        unsafe { Self::from_vec(self.as_slice().to_vec(), self.len()) }
+    }
+}
+
+impl<T: Hash, LenT: ValidLength> Hash for ThinFixedArray<T, LenT> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl<T: PartialEq, LenT: ValidLength> PartialEq for ThinFixedArray<T, LenT> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+impl<T: Eq, LenT: ValidLength> Eq for ThinFixedArray<T, LenT> {}
+
+impl<T: Debug, LenT: ValidLength> Debug for ThinFixedArray<T, LenT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        <[T] as Debug>::fmt(self, f)
+    }
+}
+
+impl<T, LenT: ValidLength> IntoIterator for ThinFixedArray<T, LenT> {
+    type Item = <Vec<T> as IntoIterator>::Item;
+    type IntoIter = <Vec<T> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_vec().into_iter()
+    }
+}
+
+impl<'a, T, LenT: ValidLength> IntoIterator for &'a ThinFixedArray<T, LenT> {
+    type Item = <&'a [T] as IntoIterator>::Item;
+    type IntoIter = <&'a [T] as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<'a, T, LenT: ValidLength> IntoIterator for &'a mut ThinFixedArray<T, LenT> {
+    type Item = <&'a mut [T] as IntoIterator>::Item;
+    type IntoIter = <&'a mut [T] as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice_mut().iter_mut()
+    }
+}
+
+impl<T, LenT: ValidLength> AsRef<[T]> for ThinFixedArray<T, LenT> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, LenT: ValidLength> From<ThinFixedArray<T, LenT>> for Vec<T> {
+    fn from(value: ThinFixedArray<T, LenT>) -> Self {
+        let mut vec = Vec::with_capacity(value.len().to_usize());
+        let mut value = core::mem::ManuallyDrop::new(value);
+
+        // SAFETY: The elements are moved into `vec`, and then only the allocation is freed.
+        unsafe {
+            let slice = value.slice_ptr();
+            core::ptr::copy_nonoverlapping(slice.as_ptr().cast(), vec.as_mut_ptr(), slice.len());
+            vec.set_len(slice.len());
+
+            if value.is_allocated() {
+                let (layout, _) = ThinFixedArray::<T, LenT>::layout(slice.len());
+                dealloc(value.header.as_ptr().cast(), layout);
+            }
+
+            value.header = NonNull::from(&EMPTY_HEADER).cast();
+        }
+
+        vec
+    }
+}
+
+impl<T, LenT: ValidLength> From<ThinFixedArray<T, LenT>> for Box<[T]> {
+    fn from(value: ThinFixedArray<T, LenT>) -> Self {
+        Vec::from(value).into_boxed_slice()
+    }
+}
+
+impl<T, LenT: ValidLength> From<ThinFixedArray<T, LenT>> for FixedArray<T, LenT> {
+    fn from(value: ThinFixedArray<T, LenT>) -> Self {
+        Vec::from(value)
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("ThinFixedArray<T, LenT> length should fit into LenT"))
+    }
+}
+
+impl<T, LenT: ValidLength> From<FixedArray<T, LenT>> for ThinFixedArray<T, LenT> {
+    fn from(value: FixedArray<T, LenT>) -> Self {
+        let len = value.len();
+
+        // SAFETY: The length is taken from the same array.
+        unsafe { Self::from_vec(value.into_vec(), len) }
+    }
+}
+
+impl<T, LenT: ValidLength> TryFrom<Vec<T>> for ThinFixedArray<T, LenT> {
+    type Error = InvalidLength<T, LenT>;
+    fn try_from(vec: Vec<T>) -> Result<Self, Self::Error> {
+        let Some(len) = LenT::from_usize(vec.len()) else {
+            return Err(InvalidLength::new(vec.into_boxed_slice()));
+        };
+
+        // SAFETY: `len` was derived from the vec length.
+        Ok(unsafe { Self::from_vec(vec, len) })
+    }
+}
+
+impl<T, LenT: ValidLength> TryFrom<Box<[T]>> for ThinFixedArray<T, LenT> {
+    type Error = InvalidLength<T, LenT>;
+    fn try_from(boxed_array: Box<[T]>) -> Result<Self, Self::Error> {
+        boxed_array.into_vec().try_into()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, LenT> serde::Deserialize<'de> for ThinFixedArray<T, LenT>
+where
+    T: serde::Deserialize<'de>,
+    LenT: ValidLength,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::try_from(Vec::<T>::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T, LenT> serde::Serialize for ThinFixedArray<T, LenT>
+where
+    T: serde::Serialize,
+    LenT: ValidLength,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "typesize")]
+impl<T: typesize::TypeSize, LenT: ValidLength> typesize::TypeSize for ThinFixedArray<T, LenT> {
+    fn extra_size(&self) -> usize {
+        if self.is_allocated() {
+            core::mem::size_of::<LenT>() + self.iter().map(T::get_size).sum::<usize>()
+        } else {
+            0
+        }
+    }
+}
+
+/// A [`FixedString`] which stores the length in the allocation, keeping only a single pointer inline.
+///
+/// This can be thought of as `Box<str>`, except the length is denoted as `LenT`, by default [`u32`],
+/// and has to be read from the allocation. `Option<ThinFixedString>` is the same size as a pointer.
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+pub struct ThinFixedString<LenT: ValidLength = SmallLen>(ThinFixedArray<u8, LenT>);
+
+impl<LenT: ValidLength> ThinFixedString<LenT> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self(ThinFixedArray::empty())
+    }
+
+    /// Returns the length of the [`ThinFixedString`].
+    ///
+    /// This reads the length from the allocation.
+    #[must_use]
+    pub fn len(&self) -> LenT {
+        self.0.len()
+    }
+
+    /// Returns if the length is equal to 0.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Converts `&`[`ThinFixedString`] to `&str`, this conversion can be performed by [`core::ops::Deref`].
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self
+    }
+
+    /// Converts [`ThinFixedString`] to [`String`], this moves the bytes into a new allocation.
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.into()
+    }
+}
+
+impl<LenT: ValidLength> core::ops::Deref for ThinFixedString<LenT> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: Self holds the type invariant that the array is UTF-8.
+        unsafe { core::str::from_utf8_unchecked(&self.0) }
+    }
+}
+
+impl<LenT: ValidLength> Default for ThinFixedString<LenT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<LenT: ValidLength> Clone for ThinFixedString<LenT> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<LenT: ValidLength> Hash for ThinFixedString<LenT> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl<LenT: ValidLength> PartialEq for ThinFixedString<LenT> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<LenT: ValidLength> Eq for ThinFixedString<LenT> {}
+
+impl<LenT: ValidLength> PartialEq<str> for ThinFixedString<LenT> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str().eq(other)
+    }
+}
+
+impl<LenT: ValidLength> PartialEq<&str> for ThinFixedString<LenT> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str().eq(*other)
+    }
+}
+
+impl<LenT: ValidLength> core::cmp::PartialOrd for ThinFixedString<LenT> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<LenT: ValidLength> core::cmp::Ord for ThinFixedString<LenT> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<LenT: ValidLength> core::fmt::Display for ThinFixedString<LenT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self)
+    }
+}
+
+impl<LenT: ValidLength> core::fmt::Debug for ThinFixedString<LenT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+impl<LenT: ValidLength> AsRef<str> for ThinFixedString<LenT> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<LenT: ValidLength> Borrow<str> for ThinFixedString<LenT> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<LenT: ValidLength> From<ThinFixedString<LenT>> for String {
+    fn from(value: ThinFixedString<LenT>) -> Self {
+        // SAFETY: Self holds the type invariant that the array is UTF-8.
+        unsafe { String::from_utf8_unchecked(value.0.into()) }
+    }
+}
+
+impl<LenT: ValidLength> From<ThinFixedString<LenT>> for FixedString<LenT> {
+    fn from(value: ThinFixedString<LenT>) -> Self {
+        if let Some(inline) = FixedString::new_inline(&value) {
+            return inline;
+        }
+
+        // SAFETY: Self holds the type invariant that the array is UTF-8.
+        unsafe { FixedString::from_array_unchecked(value.0.into()) }
+    }
+}
+
+impl<LenT: ValidLength> From<FixedString<LenT>> for ThinFixedString<LenT> {
+    fn from(value: FixedString<LenT>) -> Self {
+        let len = value.len();
+
+        // SAFETY: The length is taken from the same string, and the bytes stay UTF-8.
+        Self(unsafe { ThinFixedArray::from_vec(value.into_string().into_bytes(), len) })
+    }
+}
+
+impl<LenT: ValidLength> TryFrom<String> for ThinFixedString<LenT> {
+    type Error = InvalidStrLength<LenT>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match ThinFixedArray::try_from(value.into_bytes()) {
+            Ok(val) => Ok(Self(val)),
+            Err(err) => Err(err
+                .try_into()
+                .expect("String -> Vec<u8> should stay valid UTF8")),
+        }
+    }
+}
+
+impl<LenT: ValidLength> TryFrom<Box<str>> for ThinFixedString<LenT> {
+    type Error = InvalidStrLength<LenT>;
+
+    fn try_from(value: Box<str>) -> Result<Self, Self::Error> {
+        String::from(value).try_into()
+    }
+}
+
+impl<LenT: ValidLength> core::str::FromStr for ThinFixedString<LenT> {
+    type Err = InvalidStrLength<LenT>;
+
+    fn from_str(val: &str) -> Result<Self, Self::Err> {
+        String::from(val).try_into()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, LenT: ValidLength> serde::Deserialize<'de> for ThinFixedString<LenT> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::try_from(String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<LenT: ValidLength> serde::Serialize for ThinFixedString<LenT> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_str().serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn check_sizes() {
+        assert_eq!(core::mem::size_of::<ThinFixedArray<u8, u32>>(), 8);
+        assert_eq!(core::mem::size_of::<Option<ThinFixedArray<u8, u32>>>(), 8);
+        assert_eq!(core::mem::size_of::<Option<ThinFixedString<u32>>>(), 8);
+    }
+
+    #[test]
+    fn check_fixed_roundtrip() {
+        let original =
+            FixedString::<u8>::from_str_trunc("a string that is too long to be stored inline");
+        let thin = ThinFixedString::from(original.clone());
+        assert_eq!(thin.len(), original.len());
+        assert_eq!(thin, original.as_str());
+        assert_eq!(FixedString::from(thin), original);
+
+        let array = FixedArray::<u64, u8>::from([1, 2, 3]);
+        let thin = ThinFixedArray::from(array.clone());
+        assert_eq!(thin.len(), 3);
+        assert_eq!(thin.clone(), thin);
+        assert_eq!(FixedArray::from(thin), array);
+    }
+
+    #[test]
+    fn check_empty() {
+        let empty = ThinFixedArray::<u128, u16>::default();
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+        assert_eq!(empty.as_slice(), &[]);
+        assert!(ThinFixedArray::<u8, u32>::try_from(alloc::vec![])
+            .unwrap()
+            .is_empty());
+
+        let empty = ThinFixedString::<u32>::new();
+        assert_eq!(empty.as_str(), "");
+        assert_eq!(String::from(empty), "");
+    }
+
+    #[test]
+    fn check_length_is_checked() {
+        let err = ThinFixedArray::<u8, u8>::try_from(alloc::vec![0; 256]).unwrap_err();
+        assert_eq!(err.get_inner().len(), 256);
+
+        let err = "a".repeat(256).parse::<ThinFixedString<u8>>().unwrap_err();
+        assert_eq!(err.get_inner().len(), 256);
+    }
+}
@@ -1,6 +1,10 @@
 use alloc::{string::String, vec::Vec};
+use core::sync::atomic::{AtomicPtr, Ordering};
 
-use crate::{FixedArray, FixedString, ValidLength};
+#[cfg(feature = "unicode-segmentation")]
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{string::truncate_str, FixedArray, FixedString, ValidLength};
 
 mod sealed {
     use alloc::{string::String, vec::Vec};
@@ -15,18 +19,362 @@ mod sealed {
 ///
 /// Both of these functions may truncate the input in order to fit it into the provided [`ValidLength`],
 /// therefore this trait must be imported in order to make possible truncation made obvious in user code.
+///
+/// To truncate strings at word or grapheme boundaries, or with a suffix, see [`Truncation`]. To find out when values
+/// are truncated, see [`set_truncation_hook`].
 pub trait TruncatingInto<T>: sealed::Sealed {
     fn trunc_into(self) -> T;
 }
 
 impl<LenT: ValidLength> TruncatingInto<FixedString<LenT>> for String {
+    #[track_caller]
     fn trunc_into(self) -> FixedString<LenT> {
         FixedString::from_string_trunc(self)
     }
 }
 
 impl<T, LenT: ValidLength> TruncatingInto<FixedArray<T, LenT>> for Vec<T> {
+    #[track_caller]
     fn trunc_into(self) -> FixedArray<T, LenT> {
         FixedArray::from_vec_trunc(self)
     }
 }
+
+/// A value which was truncated to fit, passed to the hook set with [`set_truncation_hook`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TruncationEvent {
+    /// The name of the type that the value was truncated to fit into.
+    pub type_name: &'static str,
+    /// The length of the original value, in elements or bytes.
+    pub original_len: usize,
+    /// The length of the value after truncation, in elements or bytes.
+    pub truncated_len: usize,
+}
+
+/// The hook set with [`set_truncation_hook`], as a `fn(TruncationEvent)`, or null if there is none.
+static TRUNCATION_HOOK: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Sets a function to be called whenever a value is **truncated**, replacing any previous hook.
+///
+/// This is called by every truncating constructor, such as [`FixedString::from_string_trunc`] and
+/// [`TruncatingInto::trunc_into`], but not by the `_report` variants, which return the number of elements or bytes
+/// dropped instead. It is also not called by `const_from_static_trunc`, as `const fn`s cannot call it.
+///
+/// With the `strict-truncation` feature, truncation panics in debug builds, after this hook is called.
+#[allow(clippy::as_conversions)]
+pub fn set_truncation_hook(hook: fn(TruncationEvent)) {
+    TRUNCATION_HOOK.store(hook as *mut (), Ordering::Release);
+}
+
+/// Removes the hook set with [`set_truncation_hook`], returning it.
+#[must_use = "use `drop` to remove the hook without using it"]
+pub fn take_truncation_hook() -> Option<fn(TruncationEvent)> {
+    hook_from_ptr(TRUNCATION_HOOK.swap(core::ptr::null_mut(), Ordering::AcqRel))
+}
+
+fn hook_from_ptr(hook: *mut ()) -> Option<fn(TruncationEvent)> {
+    // SAFETY: Only `fn(TruncationEvent)`s are stored in `TRUNCATION_HOOK`, apart from null.
+    (!hook.is_null()).then(|| unsafe { core::mem::transmute::<*mut (), fn(TruncationEvent)>(hook) })
+}
+
+/// Calls the truncation hook, then panics if `strict-truncation` is enabled in a debug build.
+#[cold]
+#[track_caller]
+pub(crate) fn report_truncation<T: ?Sized>(original_len: usize, truncated_len: usize) {
+    let event = TruncationEvent {
+        type_name: core::any::type_name::<T>(),
+        original_len,
+        truncated_len,
+    };
+
+    if let Some(hook) = hook_from_ptr(TRUNCATION_HOOK.load(Ordering::Acquire)) {
+        hook(event);
+    }
+
+    #[cfg(all(feature = "strict-truncation", debug_assertions))]
+    panic!(
+        "{} was truncated from {original_len} to {truncated_len}, with `strict-truncation` enabled",
+        event.type_name
+    );
+}
+
+/// Panics if `strict-truncation` is enabled in a debug build, for `const fn`s which cannot call the hook.
+#[track_caller]
+pub(crate) const fn report_const_truncation() {
+    #[cfg(all(feature = "strict-truncation", debug_assertions))]
+    panic!("a static value was truncated, with `strict-truncation` enabled");
+}
+
+/// Where a [`Truncation`] may cut a string, and the unit its limit is counted in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TruncateAt {
+    /// Cuts at the last char boundary, counting the limit in bytes.
+    ///
+    /// This is how [`FixedString::from_string_trunc`] truncates.
+    Byte,
+    /// Cuts after the last whole char, counting the limit in chars.
+    Char,
+    /// Cuts after the last whole extended grapheme cluster, counting the limit in grapheme clusters.
+    ///
+    /// Unlike [`Self::Char`], this never splits emoji ZWJ sequences or combining marks from their base.
+    #[cfg(feature = "unicode-segmentation")]
+    Grapheme,
+    /// Cuts before the whitespace following the last whole word, counting the limit in chars.
+    ///
+    /// If not even the first word fits, this falls back to [`Self::Char`].
+    Word,
+}
+
+impl TruncateAt {
+    fn count(self, val: &str) -> usize {
+        match self {
+            Self::Byte => val.len(),
+            Self::Char | Self::Word => val.chars().count(),
+            #[cfg(feature = "unicode-segmentation")]
+            Self::Grapheme => val.graphemes(true).count(),
+        }
+    }
+
+    /// Returns the longest prefix of `val` which is at most `max` units and `max_bytes` bytes long.
+    fn prefix(self, val: &str, max: usize, max_bytes: usize) -> &str {
+        let longest = |unit_ends: &mut dyn Iterator<Item = usize>| {
+            let end = unit_ends
+                .take(max)
+                .take_while(|&end| end <= max_bytes)
+                .last();
+
+            &val[..end.unwrap_or(0)]
+        };
+
+        match self {
+            Self::Byte => truncate_str(val, max.min(max_bytes)),
+            Self::Char => longest(&mut val.char_indices().map(|(i, c)| i + c.len_utf8())),
+            #[cfg(feature = "unicode-segmentation")]
+            Self::Grapheme => longest(&mut val.grapheme_indices(true).map(|(i, g)| i + g.len())),
+            Self::Word => {
+                let prefix = Self::Char.prefix(val, max, max_bytes);
+                let rest = &val[prefix.len()..];
+                if rest.is_empty() {
+                    return prefix;
+                }
+
+                let words = if rest.starts_with(char::is_whitespace) {
+                    prefix
+                } else {
+                    // Drop the partial word, which starts after the last whitespace.
+                    let word_start = prefix.rfind(char::is_whitespace).unwrap_or(0);
+                    &prefix[..word_start]
+                };
+
+                match words.trim_end() {
+                    "" => prefix,
+                    words => words,
+                }
+            }
+        }
+    }
+}
+
+/// Describes how [`FixedString::from_str_trunc_with`] and [`FixedString::from_string_trunc_with`] should truncate a
+/// string which is too long.
+///
+/// A string is truncated if it is longer than `LenT::MAX` bytes, or than the limit set by [`Self::max`]. If it is,
+/// the suffix set by [`Self::suffix`] is appended, and counts towards both limits. If the suffix does not fit on its
+/// own, it is left out.
+///
+/// ```
+/// use small_fixed_array::{FixedString, TruncateAt, Truncation};
+///
+/// let truncation = Truncation::new(TruncateAt::Word).max(12).ellipsis();
+///
+/// let string = FixedString::<u8>::from_str_trunc_with("Hello there world!", truncation);
+/// assert_eq!(string, "Hello there…");
+///
+/// let string = FixedString::<u8>::from_str_trunc_with("Hello world!", truncation);
+/// assert_eq!(string, "Hello world!");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Truncation<'a> {
+    at: TruncateAt,
+    max: usize,
+    suffix: &'a str,
+}
+
+impl<'a> Truncation<'a> {
+    /// Creates a [`Truncation`] which cuts strings at `at`, with no limit apart from `LenT::MAX` bytes, and no suffix.
+    #[must_use]
+    pub const fn new(at: TruncateAt) -> Self {
+        Self {
+            at,
+            max: usize::MAX,
+            suffix: "",
+        }
+    }
+
+    /// Limits strings to `max` units, as counted by the [`TruncateAt`] this was created with.
+    #[must_use]
+    pub const fn max(self, max: usize) -> Self {
+        Self { max, ..self }
+    }
+
+    /// Appends `suffix` to strings which are truncated.
+    #[must_use]
+    pub const fn suffix(self, suffix: &'a str) -> Self {
+        Self { suffix, ..self }
+    }
+
+    /// Appends "…" to strings which are truncated.
+    #[must_use]
+    pub const fn ellipsis(self) -> Self {
+        self.suffix("…")
+    }
+
+    /// Splits `val` into the prefix to keep and the suffix to append, or returns [`None`] if it fits as is.
+    pub(crate) fn split<'s>(self, val: &'s str, max_bytes: usize) -> Option<(&'s str, &'a str)> {
+        if val.len() <= max_bytes && (self.max == usize::MAX || self.at.count(val) <= self.max) {
+            return None;
+        }
+
+        let max = self.max.checked_sub(self.at.count(self.suffix));
+        let max_bytes_with_suffix = max_bytes.checked_sub(self.suffix.len());
+        Some(match (max, max_bytes_with_suffix) {
+            (Some(max), Some(max_bytes)) => (self.at.prefix(val, max, max_bytes), self.suffix),
+            _ => (self.at.prefix(val, self.max, max_bytes), ""),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::Bounded;
+
+    fn trunc<const MAX: usize>(
+        val: &str,
+        truncation: Truncation<'_>,
+    ) -> FixedString<Bounded<u8, MAX>> {
+        let from_str = FixedString::from_str_trunc_with(val, truncation);
+        let from_string = FixedString::from_string_trunc_with(val.to_string(), truncation);
+        assert_eq!(from_str, from_string);
+        from_str
+    }
+
+    #[test]
+    fn check_truncate_at() {
+        let byte = Truncation::new(TruncateAt::Byte);
+        assert_eq!(trunc::<6>("naïve café", byte), "naïve");
+        assert_eq!(trunc::<255>("naïve café", byte.max(3)), "na");
+        assert_eq!(trunc::<255>("naïve café", byte), "naïve café");
+
+        let char = Truncation::new(TruncateAt::Char);
+        assert_eq!(trunc::<255>("naïve café", char.max(3)), "naï");
+        assert_eq!(trunc::<10>("naïve café", char.max(10)), "naïve caf");
+
+        let word = Truncation::new(TruncateAt::Word);
+        assert_eq!(trunc::<255>("naïve café", word.max(8)), "naïve");
+        assert_eq!(trunc::<255>("naïve café", word.max(6)), "naïve");
+        assert_eq!(trunc::<255>("naïve  café", word.max(7)), "naïve");
+        assert_eq!(trunc::<255>("naïve café", word.max(3)), "naï");
+        assert_eq!(trunc::<255>("  naïve", word.max(3)), "  n");
+    }
+
+    #[test]
+    fn check_suffix() {
+        let word = Truncation::new(TruncateAt::Word).ellipsis();
+        assert_eq!(
+            trunc::<255>("Hello there world!", word.max(12)),
+            "Hello there…"
+        );
+        assert_eq!(trunc::<255>("Hello there, world!", word.max(12)), "Hello…");
+        assert_eq!(
+            trunc::<255>("Hello there, world!", word.max(19)),
+            "Hello there, world!"
+        );
+        assert_eq!(trunc::<14>("Hello there world!", word), "Hello there…");
+
+        let byte = Truncation::new(TruncateAt::Byte).suffix("...");
+        assert_eq!(trunc::<8>("Hello world", byte), "Hello...");
+        assert_eq!(trunc::<2>("Hello world", byte), "He");
+        assert_eq!(trunc::<255>("Hello world", byte.max(3)), "...");
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-segmentation")]
+    fn check_graphemes() {
+        let family = "👨‍👩‍👧";
+        let grapheme = Truncation::new(TruncateAt::Grapheme);
+
+        let string = trunc::<255>(&[family, family].concat(), grapheme.max(1));
+        assert_eq!(string, family);
+
+        let string = trunc::<24>(&[family, family].concat(), grapheme);
+        assert_eq!(string, family);
+
+        let string = trunc::<255>("e\u{301}e\u{301}", grapheme.max(1).suffix("."));
+        assert_eq!(string, ".");
+        assert_eq!(trunc::<255>("ab", grapheme.max(1).ellipsis()), "…");
+    }
+
+    #[test]
+    fn check_truncation_hook() {
+        use core::sync::atomic::AtomicUsize;
+
+        static TRUNCATED: AtomicUsize = AtomicUsize::new(0);
+        static ITER_TRUNCATED: AtomicUsize = AtomicUsize::new(0);
+
+        // Other tests may truncate at the same time, so only count the types used here.
+        set_truncation_hook(|event| {
+            if event.type_name.contains("Bounded<u8, 7>") {
+                assert_eq!((event.original_len, event.truncated_len), (10, 7));
+                TRUNCATED.fetch_add(1, Ordering::Relaxed);
+            } else if event.type_name.contains("Bounded<u8, 5>") {
+                // Iterators are only polled for one item past the maximum.
+                assert_eq!((event.original_len, event.truncated_len), (6, 5));
+                ITER_TRUNCATED.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+
+        let _: FixedString<Bounded<u8, 7>> = "a".repeat(10).trunc_into();
+        let _: FixedArray<u8, Bounded<u8, 7>> = alloc::vec![0; 10].trunc_into();
+        let _ = FixedString::<Bounded<u8, 7>>::from_str_trunc_with(
+            "aaaaaaaaaa",
+            Truncation::new(TruncateAt::Char),
+        );
+        let _ = FixedString::<Bounded<u8, 7>>::from_string_trunc_report("a".repeat(10));
+        let _ = FixedString::<Bounded<u8, 7>>::from_str_trunc("short");
+
+        let _ = FixedArray::<u8, Bounded<u8, 5>>::from_iter_trunc(core::iter::repeat(0));
+        let _ = FixedString::<Bounded<u8, 5>>::from_iter_trunc(core::iter::repeat('a'));
+        let _ = FixedString::<Bounded<u8, 5>>::from_iter_trunc(core::iter::repeat('a').take(5));
+
+        assert!(take_truncation_hook().is_some());
+        assert!(take_truncation_hook().is_none());
+        assert_eq!(TRUNCATED.load(Ordering::Relaxed), 3);
+        assert_eq!(ITER_TRUNCATED.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    #[cfg(all(feature = "strict-truncation", debug_assertions))]
+    #[should_panic = "with `strict-truncation` enabled"]
+    fn check_strict_truncation() {
+        let _ = FixedString::<u8>::from_str_trunc(&"a".repeat(256));
+    }
+
+    #[test]
+    #[cfg(all(feature = "strict-truncation", debug_assertions))]
+    fn check_strict_truncation_report() {
+        let (_, dropped) = FixedString::<u8>::from_str_trunc_report(&"a".repeat(256));
+        assert_eq!(dropped, 1);
+    }
+
+    #[test]
+    #[cfg(all(feature = "strict-truncation", debug_assertions))]
+    #[should_panic = "with `strict-truncation` enabled"]
+    fn check_strict_truncation_iter() {
+        let _ = FixedArray::<u8, u8>::from_iter_trunc(core::iter::repeat(0));
+    }
+}
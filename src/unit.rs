@@ -0,0 +1,494 @@
+use alloc::{boxed::Box, string::String};
+use core::{borrow::Borrow, hash::Hash, marker::PhantomData, str::FromStr};
+
+use crate::{
+    error::InvalidUnitLength,
+    length::{SmallLen, ValidLength},
+    truncating_into::report_truncation,
+    FixedString,
+};
+
+mod sealed {
+    pub trait UnitSealed {}
+    impl UnitSealed for super::Chars {}
+    impl UnitSealed for super::Utf16 {}
+}
+
+/// A sealed trait for the units a [`UnitString`] can be measured in.
+///
+/// This is implemented on [`Chars`] and [`Utf16`].
+pub trait LengthUnit: sealed::UnitSealed {
+    /// The name of the unit, used in error messages.
+    const NAME: &'static str;
+
+    /// Returns the length of `val` in this unit.
+    #[must_use]
+    fn count(val: &str) -> usize;
+
+    /// Returns the longest prefix of `val` which is at most `max` units long.
+    #[must_use]
+    fn truncate(val: &str, max: usize) -> &str;
+}
+
+/// Measures a [`UnitString`] in Unicode scalar values, as returned by [`str::chars`].
+#[derive(Clone, Copy, Debug)]
+pub struct Chars;
+
+impl LengthUnit for Chars {
+    const NAME: &'static str = "chars";
+
+    fn count(val: &str) -> usize {
+        val.chars().count()
+    }
+
+    fn truncate(val: &str, max: usize) -> &str {
+        match val.char_indices().nth(max) {
+            Some((end, _)) => &val[..end],
+            None => val,
+        }
+    }
+}
+
+/// Measures a [`UnitString`] in UTF-16 code units, as returned by [`str::encode_utf16`].
+#[derive(Clone, Copy, Debug)]
+pub struct Utf16;
+
+impl LengthUnit for Utf16 {
+    const NAME: &'static str = "UTF-16 code units";
+
+    fn count(val: &str) -> usize {
+        val.chars().map(char::len_utf16).sum()
+    }
+
+    fn truncate(val: &str, max: usize) -> &str {
+        let mut len = 0;
+        for (end, char) in val.char_indices() {
+            len += char.len_utf16();
+            if len > max {
+                return &val[..end];
+            }
+        }
+
+        val
+    }
+}
+
+/// Returns `max` units as a `LenT`, capped at `LenT::MAX` as every unit takes at least one byte.
+fn max_units<LenT: ValidLength>(max: usize) -> LenT {
+    LenT::from_usize(max).unwrap_or(LenT::MAX)
+}
+
+/// A [`FixedString`] which is limited to `MAX` [`LengthUnit`]s, instead of `LenT::MAX` bytes.
+///
+/// The length in `Unit` is stored alongside the string, so [`Self::unit_len`] does not need to walk the string.
+/// `LenT` must still be able to hold the length in bytes, which may be up to four times `MAX`.
+///
+/// See [`CharString`] and [`Utf16String`] for the common forms.
+pub struct UnitString<Unit: LengthUnit, const MAX: usize, LenT: ValidLength = SmallLen> {
+    inner: FixedString<LenT>,
+    unit_len: LenT,
+    marker: PhantomData<Unit>,
+}
+
+/// A [`UnitString`] limited to `MAX` Unicode scalar values.
+pub type CharString<const MAX: usize, LenT = SmallLen> = UnitString<Chars, MAX, LenT>;
+
+/// A [`UnitString`] limited to `MAX` UTF-16 code units.
+pub type Utf16String<const MAX: usize, LenT = SmallLen> = UnitString<Utf16, MAX, LenT>;
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> UnitString<Unit, MAX, LenT> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inner: FixedString::new(),
+            unit_len: LenT::ZERO,
+            marker: PhantomData,
+        }
+    }
+
+    fn from_inner(inner: FixedString<LenT>, unit_len: usize) -> Self {
+        // The length in units is never larger than the length in bytes.
+        let unit_len = LenT::from_usize(unit_len)
+            .unwrap_or_else(|| unreachable!("unit length should fit into LenT"));
+
+        Self {
+            inner,
+            unit_len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Converts a `&str` into a [`UnitString`], **truncating** if the value is longer than `MAX` units.
+    ///
+    /// The value is also truncated to `LenT::MAX` bytes, as in [`FixedString::from_str_trunc`].
+    #[must_use]
+    #[track_caller]
+    pub fn from_str_trunc(val: &str) -> Self {
+        let (inner, _) = FixedString::<LenT>::from_str_trunc_report(Unit::truncate(val, MAX));
+        if inner.len().to_usize() != val.len() {
+            report_truncation::<Self>(val.len(), inner.len().to_usize());
+        }
+
+        let unit_len = Unit::count(&inner);
+        Self::from_inner(inner, unit_len)
+    }
+
+    /// Converts a [`String`] into a [`UnitString`], **truncating** if the value is longer than `MAX` units.
+    ///
+    /// The value is also truncated to `LenT::MAX` bytes, as in [`FixedString::from_string_trunc`].
+    #[must_use]
+    #[track_caller]
+    pub fn from_string_trunc(mut val: String) -> Self {
+        let original_len = val.len();
+        val.truncate(Unit::truncate(&val, MAX).len());
+
+        let (inner, _) = FixedString::<LenT>::from_string_trunc_report(val);
+        if inner.len().to_usize() != original_len {
+            report_truncation::<Self>(original_len, inner.len().to_usize());
+        }
+
+        let unit_len = Unit::count(&inner);
+        Self::from_inner(inner, unit_len)
+    }
+
+    /// Returns the length of the [`UnitString`] in `Unit`s.
+    #[must_use]
+    pub fn unit_len(&self) -> LenT {
+        self.unit_len
+    }
+
+    /// Returns the length of the [`UnitString`] in bytes.
+    #[must_use]
+    pub fn byte_len(&self) -> LenT {
+        self.inner.len()
+    }
+
+    /// Returns if the length is equal to 0.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Converts `&`[`UnitString`] to `&str`, this conversion can be performed by [`core::ops::Deref`].
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self
+    }
+
+    /// Returns a reference to the underlying [`FixedString`].
+    #[must_use]
+    pub fn as_fixed_string(&self) -> &FixedString<LenT> {
+        &self.inner
+    }
+
+    /// Converts [`UnitString`] into the underlying [`FixedString`], discarding the length in `Unit`s.
+    #[must_use]
+    pub fn into_fixed_string(self) -> FixedString<LenT> {
+        self.inner
+    }
+}
+
+impl<const MAX: usize, LenT: ValidLength> UnitString<Chars, MAX, LenT> {
+    /// Returns the length of the [`CharString`] in Unicode scalar values.
+    #[must_use]
+    pub fn char_len(&self) -> LenT {
+        self.unit_len
+    }
+}
+
+impl<const MAX: usize, LenT: ValidLength> UnitString<Utf16, MAX, LenT> {
+    /// Returns the length of the [`Utf16String`] in UTF-16 code units.
+    #[must_use]
+    pub fn utf16_len(&self) -> LenT {
+        self.unit_len
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> core::ops::Deref
+    for UnitString<Unit, MAX, LenT>
+{
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> Default
+    for UnitString<Unit, MAX, LenT>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> Clone for UnitString<Unit, MAX, LenT> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            unit_len: self.unit_len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> Hash for UnitString<Unit, MAX, LenT> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> PartialEq
+    for UnitString<Unit, MAX, LenT>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> Eq for UnitString<Unit, MAX, LenT> {}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> PartialEq<str>
+    for UnitString<Unit, MAX, LenT>
+{
+    fn eq(&self, other: &str) -> bool {
+        self.as_str().eq(other)
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> PartialEq<&str>
+    for UnitString<Unit, MAX, LenT>
+{
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str().eq(*other)
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> core::cmp::PartialOrd
+    for UnitString<Unit, MAX, LenT>
+{
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> core::cmp::Ord
+    for UnitString<Unit, MAX, LenT>
+{
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> core::fmt::Display
+    for UnitString<Unit, MAX, LenT>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self)
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> core::fmt::Debug
+    for UnitString<Unit, MAX, LenT>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> AsRef<str>
+    for UnitString<Unit, MAX, LenT>
+{
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> Borrow<str>
+    for UnitString<Unit, MAX, LenT>
+{
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> From<UnitString<Unit, MAX, LenT>>
+    for FixedString<LenT>
+{
+    fn from(value: UnitString<Unit, MAX, LenT>) -> Self {
+        value.inner
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> From<UnitString<Unit, MAX, LenT>>
+    for String
+{
+    fn from(value: UnitString<Unit, MAX, LenT>) -> Self {
+        value.inner.into()
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> TryFrom<Box<str>>
+    for UnitString<Unit, MAX, LenT>
+{
+    type Error = InvalidUnitLength<LenT>;
+
+    fn try_from(value: Box<str>) -> Result<Self, Self::Error> {
+        let unit_len = Unit::count(&value);
+        if unit_len > MAX {
+            let type_name = core::any::type_name::<Self>();
+            return Err(InvalidUnitLength::new(
+                type_name,
+                Unit::NAME,
+                max_units::<LenT>(MAX),
+                unit_len,
+                value,
+            ));
+        }
+
+        let inner = FixedString::try_from(value)?;
+        Ok(Self::from_inner(inner, unit_len))
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> TryFrom<String>
+    for UnitString<Unit, MAX, LenT>
+{
+    type Error = InvalidUnitLength<LenT>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.into_boxed_str().try_into()
+    }
+}
+
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> FromStr
+    for UnitString<Unit, MAX, LenT>
+{
+    type Err = InvalidUnitLength<LenT>;
+
+    fn from_str(val: &str) -> Result<Self, Self::Err> {
+        let unit_len = Unit::count(val);
+        if unit_len > MAX {
+            let type_name = core::any::type_name::<Self>();
+            return Err(InvalidUnitLength::new(
+                type_name,
+                Unit::NAME,
+                max_units::<LenT>(MAX),
+                unit_len,
+                val.into(),
+            ));
+        }
+
+        let inner = FixedString::from_str(val)?;
+        Ok(Self::from_inner(inner, unit_len))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Unit: LengthUnit, const MAX: usize, LenT: ValidLength> serde::Deserialize<'de>
+    for UnitString<Unit, MAX, LenT>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor<Unit, const MAX: usize, LenT>(PhantomData<(Unit, LenT)>);
+
+        impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> serde::de::Visitor<'_>
+            for Visitor<Unit, MAX, LenT>
+        {
+            type Value = UnitString<Unit, MAX, LenT>;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(formatter, "a string up to {MAX} {} long", Unit::NAME)
+            }
+
+            fn visit_str<E: serde::de::Error>(self, val: &str) -> Result<Self::Value, E> {
+                UnitString::from_str(val).map_err(E::custom)
+            }
+
+            fn visit_string<E: serde::de::Error>(self, val: String) -> Result<Self::Value, E> {
+                UnitString::try_from(val).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_string(Visitor(PhantomData))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> serde::Serialize
+    for UnitString<Unit, MAX, LenT>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_str().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "typesize")]
+impl<Unit: LengthUnit, const MAX: usize, LenT: ValidLength> typesize::TypeSize
+    for UnitString<Unit, MAX, LenT>
+{
+    fn extra_size(&self) -> usize {
+        self.inner.extra_size()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check_char_limit() {
+        // 4 chars, 16 bytes.
+        let crabs = "🦀🦀🦀🦀";
+
+        let string = CharString::<4, u8>::from_str(crabs).unwrap();
+        assert_eq!(string.char_len(), 4);
+        assert_eq!(string.byte_len(), 16);
+
+        assert!(CharString::<3, u8>::from_str(crabs).is_err());
+        assert_eq!(
+            CharString::<3, u8>::from_str_trunc(crabs).as_str(),
+            "🦀🦀🦀"
+        );
+        assert_eq!(
+            CharString::<3, u8>::from_string_trunc(crabs.into()).char_len(),
+            3
+        );
+    }
+
+    #[test]
+    fn check_utf16_limit() {
+        // 4 UTF-16 code units, as the crab is a surrogate pair.
+        let value = "a🦀b";
+
+        let string = Utf16String::<4, u8>::from_str(value).unwrap();
+        assert_eq!(string.utf16_len(), 4);
+        assert_eq!(
+            usize::from(string.utf16_len()),
+            value.encode_utf16().count()
+        );
+
+        assert!(Utf16String::<3, u8>::from_str(value).is_err());
+        assert_eq!(Utf16String::<2, u8>::from_str_trunc(value).as_str(), "a");
+        assert_eq!(Utf16String::<3, u8>::from_str_trunc(value).as_str(), "a🦀");
+    }
+
+    #[test]
+    fn check_byte_limit() {
+        let value = "a".repeat(300);
+
+        assert!(CharString::<300, u8>::try_from(value.clone()).is_err());
+
+        let truncated = CharString::<300, u8>::from_string_trunc(value);
+        assert_eq!(truncated.char_len(), u8::MAX);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn check_serde_limit() {
+        let short: CharString<4, u8> = serde_json::from_str("\"🦀🦀🦀🦀\"").unwrap();
+        assert_eq!(short.char_len(), 4);
+
+        assert!(serde_json::from_str::<CharString<3, u8>>("\"🦀🦀🦀🦀\"").is_err());
+        assert!(serde_json::from_str::<Utf16String<7, u8>>("\"🦀🦀🦀🦀\"").is_err());
+    }
+}